@@ -6,20 +6,48 @@ use std::path::Path;
 
 fn main() {
     let sources = [
-        ("data/resources/assets/xkcd.txt", "XKCD", "XKCD_VALUES"),
-        ("data/resources/assets/basic.txt", "BASIC", "BASIC_VALUES"),
-        ("data/resources/assets/svg.txt", "SVG", "SVG_VALUES"),
-        ("data/resources/assets/gnome.txt", "GNOME", "GNOME_VALUES"),
+        (
+            "data/resources/assets/xkcd.txt",
+            "XKCD",
+            "XKCD_VALUES",
+            "XKCD_TREE",
+        ),
+        (
+            "data/resources/assets/basic.txt",
+            "BASIC",
+            "BASIC_VALUES",
+            "BASIC_TREE",
+        ),
+        (
+            "data/resources/assets/svg.txt",
+            "SVG",
+            "SVG_VALUES",
+            "SVG_TREE",
+        ),
+        (
+            "data/resources/assets/gnome.txt",
+            "GNOME",
+            "GNOME_VALUES",
+            "GNOME_TREE",
+        ),
+        (
+            "data/resources/assets/iscc-nbs.txt",
+            "ISCC_NBS",
+            "ISCC_NBS_VALUES",
+            "ISCC_NBS_TREE",
+        ),
     ];
 
     let out_dir = env::var_os("OUT_DIR").unwrap();
     let path = Path::new(&out_dir).join("codegen.rs");
     let mut file = BufWriter::new(File::create(path).expect("Failed to create map file"));
 
-    sources.iter().for_each(|(path, name, rev_name)| {
-        println!("cargo:rerun-if-changed={}", path);
-        generate_map(&mut file, path, name, rev_name).expect("Failed to write map")
-    });
+    sources
+        .iter()
+        .for_each(|(path, name, rev_name, tree_name)| {
+            println!("cargo:rerun-if-changed={}", path);
+            generate_map(&mut file, path, name, rev_name, tree_name).expect("Failed to write map")
+        });
 
     println!("cargo:rerun-if-changed=build.rs");
 }
@@ -29,26 +57,13 @@ fn generate_map<T: AsRef<Path>>(
     path: T,
     name: &str,
     rev_name: &str,
+    tree_name: &str,
 ) -> Result<(), io::Error> {
-    // the resulting map must have unique key
-    // some colors have multiple names, so they need to be removed
-    // these should (hopefully) be the less used ones
-    const DUPLICATED_COLORS: [&str; 10] = [
-        "aqua",           //conflicts with cyan
-        "darkgray",       //conflicts with darkgrey
-        "darkslategray",  //conflicts with darkslategrey
-        "dimgray",        //conflicts with dimgrey
-        "gray",           //conflicts with grey
-        "olive",          //conflicts with grey
-        "lightgray",      //conflicts with lightgrey
-        "lightslategray", //conflicts with lightslategrey
-        "fuchsia",        //conflicts with magenta
-        "slategray",      //conflicts with slategrey
-    ];
-
     let input_file = std::fs::read_to_string(path)?;
     let mut map = phf_codegen::Map::new();
-    let mut reverse_map = phf_codegen::Map::new();
+    // some colors have multiple names, such as cyan and aqua, which are kept in the order of the file
+    let mut aliases: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut points = Vec::new();
 
     input_file
         .lines()
@@ -58,27 +73,123 @@ fn generate_map<T: AsRef<Path>>(
         .for_each(|(name, hex)| {
             map.entry(name.to_ascii_lowercase(), &format!("\"{}ff\"", hex));
 
-            if !DUPLICATED_COLORS.contains(&name) {
-                reverse_map.entry(
-                    format!("{}ff", hex.to_ascii_lowercase()),
-                    &format!("\"{}\"", name),
-                );
+            let key = format!("{}ff", hex.to_ascii_lowercase());
+            match aliases.iter_mut().find(|(other, _)| *other == key) {
+                Some((_, names)) => names.push(name),
+                None => {
+                    aliases.push((key, vec![name]));
+                    // the tree only needs each color once, its aliases are found using the reverse map
+                    match Point::new(name, hex) {
+                        Some(point) => points.push(point),
+                        None => println!("cargo:warning=Invalid hex code {} for {}", hex, name),
+                    }
+                }
             }
         });
 
-    write_map(file, name, map)?;
-    write_map(file, rev_name, reverse_map)
+    let mut reverse_map = phf_codegen::Map::new();
+    for (key, names) in aliases {
+        reverse_map.entry(key, &format!("&{:?}", names));
+    }
+
+    write_map(file, name, "&'static str", map)?;
+    write_map(file, rev_name, "&'static [&'static str]", reverse_map)?;
+    write_tree(file, tree_name, points)
+}
+
+/// A named color with its precomputed Oklab coordinates.
+struct Point {
+    name: String,
+    rgb: [u8; 3],
+    oklab: [f64; 3],
+}
+
+impl Point {
+    /// Returns `None` if the hex code does not consist of exactly six digits.
+    fn new(name: &str, hex: &str) -> Option<Self> {
+        let hex = hex.trim_start_matches('#');
+        if hex.len() != 6 {
+            return None;
+        }
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let [_, red, green, blue] = value.to_be_bytes();
+        Some(Self {
+            name: name.to_owned(),
+            rgb: [red, green, blue],
+            oklab: oklab([red, green, blue]),
+        })
+    }
+}
+
+/// Converts a sRGB color to Oklab, see <https://bottosson.github.io/posts/oklab/>.
+fn oklab(rgb: [u8; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb.map(|value| {
+        let value = value as f64 / 255.0;
+        if value <= 0.04045 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    });
+
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+/// Orders the points as an implicit k-d tree, by splitting at the median of the axes
+/// lightness, a and b in turn.
+///
+/// The median of each slice is its root, followed by the left and right subtrees before and after it.
+fn build_tree(points: &mut [Point], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 3;
+    points.sort_by(|a, b| a.oklab[axis].total_cmp(&b.oklab[axis]));
+    let median = points.len() / 2;
+    let (left, right) = points.split_at_mut(median);
+    build_tree(left, depth + 1);
+    build_tree(&mut right[1..], depth + 1);
+}
+
+fn write_tree(
+    file: &mut BufWriter<File>,
+    name: &str,
+    mut points: Vec<Point>,
+) -> Result<(), io::Error> {
+    build_tree(&mut points, 0);
+
+    writeln!(file, "static {}: [NamedColor; {}] = [", name, points.len())?;
+    for point in points {
+        let [l, a, b] = point.oklab;
+        writeln!(
+            file,
+            "    NamedColor {{ name: {:?}, rgb: {:?}, oklab: [{:?}, {:?}, {:?}] }},",
+            point.name, point.rgb, l as f32, a as f32, b as f32
+        )?;
+    }
+    writeln!(file, "];")
 }
 
 fn write_map(
     file: &mut BufWriter<File>,
     name: &str,
+    value_type: &str,
     map: phf_codegen::Map<String>,
 ) -> Result<(), io::Error> {
     write!(
         file,
-        "const {}: phf::Map<&'static str, &'static str> = {}",
+        "const {}: phf::Map<&'static str, {}> = {}",
         name,
+        value_type,
         map.build()
     )?;
     writeln!(file, ";")
@@ -8,6 +8,7 @@ use gtk::{gdk, gio, glib};
 use search_provider::{IconData, ResultID, ResultMeta, SearchProviderImpl};
 
 use crate::colors::color::Color;
+use crate::colors::Notation;
 use crate::config::{APP_ID, PKGDATADIR, PROFILE, VERSION};
 use crate::widgets::about_window::EyedropperAbout;
 use crate::widgets::preferences::preferences_window::PreferencesWindow;
@@ -160,6 +161,13 @@ impl App {
             })
             .build();
 
+        // Paste a color in any notation from the clipboard
+        let action_paste_color = gio::ActionEntry::builder("paste_color")
+            .activate(|app: &Self, _, _| {
+                app.main_window().paste_color();
+            })
+            .build();
+
         // Preferences
         let action_preferences = gio::ActionEntry::builder("preferences")
             .activate(|app: &Self, _, _| {
@@ -187,6 +195,7 @@ impl App {
             action_pick_color,
             action_clear_history,
             action_random_color,
+            action_paste_color,
             action_preferences,
             action_quit,
             action_about,
@@ -197,6 +206,7 @@ impl App {
     fn setup_accels(&self) {
         self.set_accels_for_action("app.pick_color", &["<Control>p"]);
         self.set_accels_for_action("app.random_color", &["<Control>r"]);
+        self.set_accels_for_action("app.paste_color", &["<Control>v"]);
         self.set_accels_for_action("app.preferences", &["<Control>comma"]);
         self.set_accels_for_action("app.quit", &["<Control>w", "<Control>q"]);
     }
@@ -271,11 +281,19 @@ impl SearchProviderImpl for App {
     }
 
     fn initial_result_set(&self, terms: &[String]) -> Vec<ResultID> {
-        terms
-            .iter()
-            .filter_map(|term| gdk::RGBA::parse(term).ok())
-            .map(|color| color.to_string())
-            .collect::<Vec<_>>()
+        // the terms are split at whitespace, so also try the whole query for notations like `rgb(46 52 64)`,
+        // ignoring notations such as plain numbers, which would turn any calculation or year into a color
+        let mut results = Vec::new();
+        for result in std::iter::once(terms.join(" "))
+            .chain(terms.iter().cloned())
+            .filter_map(|term| Notation::detect_unmistakable(&term))
+            .map(|detected| gdk::RGBA::from(detected.color).to_string())
+        {
+            if !results.contains(&result) {
+                results.push(result);
+            }
+        }
+        results
     }
 
     fn result_metas(&self, identifiers: &[ResultID]) -> Vec<ResultMeta> {
@@ -0,0 +1,156 @@
+use palette::{convert::FromColorUnclamped, white_point::D65, LinSrgb, Srgb, WithAlpha, Xyz};
+
+use super::color::Color;
+
+/// The lowest temperature in Kelvin, for which the approximation of the Planckian locus is accurate.
+pub const MIN_TEMPERATURE: f32 = 1000.0;
+/// The highest temperature in Kelvin, for which the approximation of the Planckian locus is accurate.
+pub const MAX_TEMPERATURE: f32 = 15000.0;
+/// The largest distance from the Planckian locus, for which a color temperature is meaningful.
+pub const MAX_DUV: f32 = 0.05;
+
+/// The correlated color temperature (CCT) of a color and its distance (Duv) from the Planckian locus.
+///
+/// The temperature is the one of the closest black body radiator in the CIE 1960 UCS diagram. The
+/// Duv is positive above the locus, towards green, and negative below it, towards magenta.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cct {
+    /// The temperature in Kelvin.
+    pub temperature: f32,
+    pub duv: f32,
+}
+
+/// Returns the CIE 1960 uv chromaticity of a black body radiator at the given temperature.
+///
+/// Uses the approximation of Krystek (1985), which is accurate between 1000K and 15000K.
+fn planckian_uv(temperature: f64) -> (f64, f64) {
+    let t = temperature;
+    let u = (0.860_117_757 + 1.541_182_54e-4 * t + 1.286_412_12e-7 * t * t)
+        / (1.0 + 8.424_202_35e-4 * t + 7.081_451_63e-7 * t * t);
+    let v = (0.317_398_726 + 4.228_062_45e-5 * t + 4.204_816_91e-8 * t * t)
+        / (1.0 - 2.897_418_16e-5 * t + 1.614_560_53e-7 * t * t);
+    (u, v)
+}
+
+impl Cct {
+    /// Returns the color temperature of the color, if it is close enough to the Planckian locus.
+    pub fn from_xyz(xyz: Xyz<D65, f32>) -> Option<Self> {
+        let (x, y, z) = (xyz.x as f64, xyz.y as f64, xyz.z as f64);
+        let denominator = x + 15.0 * y + 3.0 * z;
+        if denominator <= 0.0 {
+            return None;
+        }
+        let (u, v) = (4.0 * x / denominator, 6.0 * y / denominator);
+
+        // the locus is more uniform when using the reciprocal temperature (mired)
+        let distance = |mired: f64| {
+            let (locus_u, locus_v) = planckian_uv(1e6 / mired);
+            (u - locus_u).hypot(v - locus_v)
+        };
+        let (min_mired, max_mired) = (1e6 / MAX_TEMPERATURE as f64, 1e6 / MIN_TEMPERATURE as f64);
+
+        // find the closest mired roughly, then refine it with a ternary search
+        let mut closest = min_mired;
+        let mut mired = min_mired;
+        while mired <= max_mired {
+            if distance(mired) < distance(closest) {
+                closest = mired;
+            }
+            mired += 1.0;
+        }
+        let (mut low, mut high) = (
+            (closest - 1.0).max(min_mired),
+            (closest + 1.0).min(max_mired),
+        );
+        for _ in 0..100 {
+            let (first, second) = (low + (high - low) / 3.0, high - (high - low) / 3.0);
+            if distance(first) < distance(second) {
+                high = second;
+            } else {
+                low = first;
+            }
+        }
+        let mired = (low + high) / 2.0;
+
+        let (_, locus_v) = planckian_uv(1e6 / mired);
+        let duv = distance(mired).copysign(v - locus_v) as f32;
+        let temperature = (1e6 / mired) as f32;
+
+        // the closest temperature is at the end of the range, so the color lies beyond it
+        let is_at_boundary = mired - min_mired < 1e-6 || max_mired - mired < 1e-6;
+        if duv.abs() > MAX_DUV || is_at_boundary {
+            return None;
+        }
+
+        Some(Self { temperature, duv })
+    }
+
+    /// Returns the brightest color within the sRGB gamut, that has this color temperature.
+    ///
+    /// Colors with the same chromaticity have the same temperature,
+    /// so the luminance can not be derived from it.
+    pub fn color(&self) -> Color {
+        const DELTA: f64 = 0.01;
+        let temperature = self.temperature as f64;
+        let (u, v) = planckian_uv(temperature);
+
+        // move perpendicular to the locus, so the closest point on the locus stays the same
+        let (next_u, next_v) = planckian_uv(temperature + DELTA);
+        let (tangent_u, tangent_v) = (next_u - u, next_v - v);
+        let length = tangent_u.hypot(tangent_v);
+        let (normal_u, normal_v) = if tangent_u < 0.0 {
+            (tangent_v / length, -tangent_u / length)
+        } else {
+            (-tangent_v / length, tangent_u / length)
+        };
+        let duv = self.duv as f64;
+        let (u, v) = (u + duv * normal_u, v + duv * normal_v);
+
+        let (x, y) = (
+            3.0 * u / (2.0 * u - 8.0 * v + 4.0),
+            2.0 * v / (2.0 * u - 8.0 * v + 4.0),
+        );
+        let xyz = Xyz::<D65, f32>::new((x / y) as f32, 1.0, ((1.0 - x - y) / y) as f32);
+
+        let linear = LinSrgb::from_color_unclamped(xyz);
+        let max = linear.red.max(linear.green).max(linear.blue);
+        let srgb = Srgb::from_linear(linear / max);
+        Color::from_palette_unclamped(srgb.with_alpha(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use palette::convert::IntoColorUnclamped;
+
+    use super::*;
+
+    fn cct(color: Color) -> Option<Cct> {
+        Cct::from_xyz(color.color.into_color_unclamped())
+    }
+
+    #[test]
+    fn finds_temperature_of_white() {
+        // the D65 white point is slightly above the locus
+        let cct = cct(Color::rgb(255, 255, 255)).unwrap();
+        assert!((cct.temperature - 6504.0).abs() < 2.0, "{cct:?}");
+        assert!((cct.duv - 0.0032).abs() < 1e-4, "{cct:?}");
+    }
+
+    #[test]
+    fn rejects_colors_far_from_the_locus() {
+        assert_eq!(None, cct(Color::rgb(255, 0, 255)));
+        assert_eq!(None, cct(Color::rgb(0, 255, 0)));
+        assert_eq!(None, cct(Color::rgb(0, 0, 0)));
+    }
+
+    #[test]
+    fn round_trips() {
+        for (temperature, duv) in [(2700.0, 0.0), (4000.0, -0.005), (6500.0, 0.01)] {
+            let color = Cct { temperature, duv }.color();
+            let cct = cct(color).unwrap();
+            assert!((cct.temperature - temperature).abs() < 0.1, "{cct:?}");
+            assert!((cct.duv - duv).abs() < 1e-5, "{cct:?}");
+        }
+    }
+}
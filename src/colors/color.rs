@@ -1,9 +1,33 @@
 use core::fmt;
-use std::str::FromStr;
+use std::{borrow::Cow, str::FromStr};
 
-use palette::IntoColor;
+use glib::{FromVariant, StaticVariantType, ToVariant, Variant, VariantTy};
 
-use super::parser;
+use palette::{
+    cam16::Cam16,
+    convert::{FromColorUnclamped, IntoColorUnclamped},
+    white_point::{Any, D65, E},
+    Lab, Laba, Luva, Srgb, WithAlpha, Xyz, Xyza, Yxya,
+};
+
+use super::{
+    illuminant::ReferenceWhite,
+    matrix::{self, Matrix3},
+    parser,
+    viewing_conditions::ViewingConditions,
+};
+
+/// The Hunt-Pointer-Estévez matrix, used to convert XYZ values to LMS.
+const HUNT_POINTER_ESTEVEZ: Matrix3 = [
+    [0.3897, 0.6890, -0.0787],
+    [-0.2298, 1.1834, 0.0464],
+    [0.0, 0.0, 1.0],
+];
+
+/// The boundary between the linear and the cube root part of the CIE lightness, `(6/29)^3`.
+const CIE_EPSILON: f32 = 216.0 / 24389.0;
+/// The slope of the linear part of the CIE lightness, `(29/3)^3`.
+const CIE_KAPPA: f32 = 24389.0 / 27.0;
 
 /// Eyedropper's internal color representation.
 ///
@@ -26,7 +50,19 @@ impl std::ops::DerefMut for Color {
 }
 
 impl Color {
-    /// Create a new Color object with an alpha value.
+    /// Create a new Color object from floating point components.
+    ///
+    /// The components are not clamped, so colors outside of the sRGB gamut can be represented.
+    pub fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self(palette::Srgba::new(red, green, blue, alpha))
+    }
+
+    /// Create a new, fully opaque Color object from 8-bit components.
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::rgba(red, green, blue, 255)
+    }
+
+    /// Create a new Color object from 8-bit components with an alpha value.
     pub fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
         Self(palette::Srgba::new(
             red as f32 / 255.0,
@@ -53,64 +89,248 @@ impl Color {
         Self(color.into_color())
     }
 
+    /// Create a color without clamping it to the sRGB gamut.
+    ///
+    /// This should be used for color spaces, that are able to represent colors outside of sRGB.
+    pub fn from_palette_unclamped(color: impl IntoColorUnclamped<palette::Srgba>) -> Self {
+        Self(color.into_color_unclamped())
+    }
+
+    /// Whether the color lies within the sRGB gamut.
+    ///
+    /// Colors outside of it will be clamped, when they are displayed in sRGB based formats.
+    pub fn is_in_srgb_gamut(&self) -> bool {
+        // allow for floating point errors in the conversions
+        const TOLERANCE: f32 = 1e-4;
+        [self.color.red, self.color.green, self.color.blue]
+            .iter()
+            .all(|component| (-TOLERANCE..=1.0 + TOLERANCE).contains(component))
+    }
+
+    /// Returns the red, green, blue and alpha components as 8-bit values.
+    ///
+    /// The components are clamped to the sRGB gamut and rounded half up. This should only be used
+    /// for formatting, as the color itself keeps its full precision.
+    pub fn rgba8(&self) -> [u8; 4] {
+        [
+            self.color.red,
+            self.color.green,
+            self.color.blue,
+            self.alpha,
+        ]
+        .map(|value| round_half_up(value.clamp(0.0, 1.0) * 255.0) as u8)
+    }
+
     pub fn hex(&self) -> String {
-        format!(
-            "#{:02x}{:02x}{:02x}{:02x}",
-            (self.color.red * 255.0) as u8,
-            (self.color.green * 255.0) as u8,
-            (self.color.blue * 255.0) as u8,
-            (self.alpha * 255.0) as u8,
+        let [red, green, blue, alpha] = self.rgba8();
+        format!("#{:02x}{:02x}{:02x}{:02x}", red, green, blue, alpha)
+    }
+
+    /// Convert the color to the CIE XYZ color space, relative to the given reference white.
+    ///
+    /// The values are scaled so that the reference white has a luminance of `Y = 1.0`.
+    /// If the reference white differs from the one of sRGB, the color is adapted using the
+    /// Bradford transform.
+    pub fn to_xyz(self, reference_white: ReferenceWhite) -> Xyza<Any> {
+        let xyz: palette::Xyz = self.color.into_color_unclamped();
+        reference_white
+            .adapt_from(xyz.with_white_point(), ReferenceWhite::srgb())
+            .with_alpha(self.alpha)
+    }
+
+    /// Create a color from CIE XYZ values, relative to the given reference white.
+    pub fn from_xyz(xyz: Xyza<Any>, reference_white: ReferenceWhite) -> Self {
+        let adapted = ReferenceWhite::srgb().adapt_from(xyz.color, reference_white);
+        Color::from_palette_unclamped(adapted.with_white_point::<D65>().with_alpha(xyz.alpha))
+    }
+
+    /// Convert the color to CIE xyY values, relative to the given reference white.
+    ///
+    /// Black has no chromaticity, so the chromaticity of the reference white is used instead.
+    pub fn to_xyy(self, reference_white: ReferenceWhite) -> Yxya<Any> {
+        let xyz = self.to_xyz(reference_white);
+        let sum = xyz.x + xyz.y + xyz.z;
+        let (x, y) = if sum <= 0.0 {
+            chromaticity_xy(reference_white.xyz())
+        } else {
+            chromaticity_xy(xyz.color)
+        };
+        Yxya::new(x, y, xyz.y, xyz.alpha)
+    }
+
+    /// Create a color from CIE xyY values, relative to the given reference white.
+    pub fn from_xyy(xyy: Yxya<Any>, reference_white: ReferenceWhite) -> Self {
+        if xyy.y <= 0.0 {
+            return Color::from_xyz(Xyza::new(0.0, 0.0, 0.0, xyy.alpha), reference_white);
+        }
+
+        let scale = xyy.luma / xyy.y;
+        Color::from_xyz(
+            Xyza::new(
+                xyy.x * scale,
+                xyy.luma,
+                (1.0 - xyy.x - xyy.y) * scale,
+                xyy.alpha,
+            ),
+            reference_white,
+        )
+    }
+
+    /// Convert the color to the CIELAB color space, relative to the given reference white.
+    pub fn to_lab(self, reference_white: ReferenceWhite) -> Laba<Any> {
+        // CIELAB only depends on the ratio to the reference white, so after normalizing
+        // the values we can reuse palette's conversion for the equal energy white point
+        let xyz = self.to_xyz(reference_white);
+        let white = reference_white.xyz();
+        let lab = Lab::<E>::from_color_unclamped(Xyz::<E>::new(
+            xyz.x / white.x,
+            xyz.y / white.y,
+            xyz.z / white.z,
+        ));
+
+        Laba::new(lab.l, lab.a, lab.b, xyz.alpha)
+    }
+
+    /// Create a color from CIELAB values, relative to the given reference white.
+    pub fn from_lab(lab: Laba<Any>, reference_white: ReferenceWhite) -> Self {
+        let xyz = Xyz::<E>::from_color_unclamped(Lab::<E>::new(lab.l, lab.a, lab.b));
+        let white = reference_white.xyz();
+
+        Color::from_xyz(
+            Xyza::new(xyz.x * white.x, xyz.y * white.y, xyz.z * white.z, lab.alpha),
+            reference_white,
+        )
+    }
+
+    /// Convert the color to the CIELUV color space, relative to the given reference white.
+    pub fn to_luv(self, reference_white: ReferenceWhite) -> Luva<Any> {
+        let xyz = self.to_xyz(reference_white);
+        let white = reference_white.xyz();
+
+        let y = xyz.y / white.y;
+        let l = if y > CIE_EPSILON {
+            116.0 * y.cbrt() - 16.0
+        } else {
+            CIE_KAPPA * y
+        };
+
+        let (u, v) = chromaticity_uv(xyz.color);
+        let (u_white, v_white) = chromaticity_uv(white);
+
+        Luva::new(
+            l,
+            13.0 * l * (u - u_white),
+            13.0 * l * (v - v_white),
+            xyz.alpha,
         )
     }
 
+    /// Create a color from CIELUV values, relative to the given reference white.
+    pub fn from_luv(luv: Luva<Any>, reference_white: ReferenceWhite) -> Self {
+        if luv.l <= 0.0 {
+            return Color::from_xyz(Xyza::new(0.0, 0.0, 0.0, luv.alpha), reference_white);
+        }
+
+        let white = reference_white.xyz();
+        let (u_white, v_white) = chromaticity_uv(white);
+        let u = luv.u / (13.0 * luv.l) + u_white;
+        let v = luv.v / (13.0 * luv.l) + v_white;
+
+        let y = if luv.l > CIE_KAPPA * CIE_EPSILON {
+            ((luv.l + 16.0) / 116.0).powi(3)
+        } else {
+            luv.l / CIE_KAPPA
+        } * white.y;
+        let x = y * 9.0 * u / (4.0 * v);
+        let z = y * (12.0 - 3.0 * u - 20.0 * v) / (4.0 * v);
+
+        Color::from_xyz(Xyza::new(x, y, z, luv.alpha), reference_white)
+    }
+
     /// Convert the color to the LMS color space.
     ///
     /// LMS (long, medium short) is a a color space, that
     /// represents the cones in the human eyes.
     ///
     /// The conversion uses the formula form [Fundamentals of Imaging Colour Spaces](https://www.uni-weimar.de/fileadmin/user/fak/medien/professuren/Computer_Graphics/3-ima-color-spaces17.pdf)
-    /// The matrix is assumed to be under [Illuminant::E].
-    pub fn to_lms(self) -> (f32, f32, f32) {
+    /// The Hunt-Pointer-Estévez matrix is applied to the XYZ values relative to the given reference white.
+    pub fn to_lms(self, reference_white: ReferenceWhite) -> (f32, f32, f32) {
         //TODO: remove this once palette supports LMS in the next version
-        let xyz: palette::Xyz = self.color.into_color();
-
-        //TODO: use illuminant depended matrices, found here http://brucelindbloom.com/index.html?Eqn_ChromAdapt.html
-        let long = xyz.x * 0.3897 + xyz.y * 0.6890 + xyz.z * -0.0787;
-        let medium = xyz.x * -0.2298 + xyz.y * 1.1834 + xyz.z * 0.0464;
-        let short = xyz.x * 0.0 + xyz.y * 0.0 + xyz.z * 1.0;
+        let xyz = self.to_xyz(reference_white);
+        let [long, medium, short] = matrix::multiply(HUNT_POINTER_ESTEVEZ, [xyz.x, xyz.y, xyz.z]);
 
         (long, medium, short)
     }
 
-    pub fn from_lms(long: f32, medium: f32, short: f32, alpha: u8) -> Self {
-        let x = long * 1.9102 + medium * -1.1121 + short * 0.2019;
-        let y = long * 0.3710 + medium * 0.6291 + short * 0.0;
-        let z = long * 0.0 + medium * 0.0 + short * 1.0;
+    pub fn from_lms(
+        long: f32,
+        medium: f32,
+        short: f32,
+        alpha: f32,
+        reference_white: ReferenceWhite,
+    ) -> Self {
+        // invert the matrix instead of using the rounded published inverse,
+        // so converting back and forth results in the same color
+        let [x, y, z] =
+            matrix::multiply(matrix::invert(HUNT_POINTER_ESTEVEZ), [long, medium, short]);
 
-        Color::from_palette(palette::Xyza::new(x, y, z, alpha as f32 / 255.0))
+        Color::from_xyz(Xyza::new(x, y, z, alpha), reference_white)
     }
+
+    /// Derive the CAM16 attributes of the color under the given viewing conditions.
+    ///
+    /// The model is computed with double precision, as its many non linear steps
+    /// would otherwise accumulate visible rounding errors.
+    pub fn to_cam16(self, viewing_conditions: ViewingConditions) -> Cam16<f64> {
+        let srgb: Srgb<f64> = self.color.into_format();
+        Cam16::from_xyz(srgb.into_color_unclamped(), viewing_conditions.parameters())
+    }
+
+    /// Create a color from its CAM16 attributes under the given viewing conditions.
+    pub fn from_cam16(
+        cam16: Cam16<f64>,
+        alpha: f32,
+        viewing_conditions: ViewingConditions,
+    ) -> Self {
+        let xyz: Xyz<D65, f64> = cam16.into_xyz(viewing_conditions.parameters());
+        let srgb: Srgb<f64> = xyz.into_color_unclamped();
+        Color::from_palette_unclamped(srgb.into_format::<f32>().with_alpha(alpha))
+    }
+}
+
+/// Returns the u' and v' chromaticity coordinates of the CIE 1976 UCS diagram.
+fn chromaticity_uv(xyz: Xyz<Any, f32>) -> (f32, f32) {
+    let denominator = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
+    if denominator == 0.0 {
+        return (0.0, 0.0);
+    }
+    (4.0 * xyz.x / denominator, 9.0 * xyz.y / denominator)
+}
+
+/// Returns the x and y chromaticity coordinates of the CIE 1931 xy diagram.
+fn chromaticity_xy(xyz: Xyz<Any, f32>) -> (f32, f32) {
+    let sum = xyz.x + xyz.y + xyz.z;
+    (xyz.x / sum, xyz.y / sum)
+}
+
+/// Rounds the value to the nearest integer, rounding ties up.
+///
+/// Used for all rounding when displaying colors, so that the same color always results in the same
+/// values. Unlike [`f32::round`], ties of negative values are rounded towards positive infinity.
+pub fn round_half_up(value: f32) -> f32 {
+    (value + 0.5).floor()
 }
 
 impl fmt::Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "#{:02x}{:02x}{:02x}",
-            (self.color.red * 255.0) as u8,
-            (self.color.green * 255.0) as u8,
-            (self.color.blue * 255.0) as u8,
-        )
+        let [red, green, blue, _] = self.rgba8();
+        write!(f, "#{:02x}{:02x}{:02x}", red, green, blue)
     }
 }
 
 impl From<gtk::gdk::RGBA> for Color {
     fn from(color: gtk::gdk::RGBA) -> Self {
-        Color::rgba(
-            (255f32 * color.red()) as u8,
-            (255f32 * color.green()) as u8,
-            (255f32 * color.blue()) as u8,
-            (255f32 * color.alpha()) as u8,
-        )
+        Color::new(color.red(), color.green(), color.blue(), color.alpha())
     }
 }
 
@@ -120,6 +340,38 @@ impl From<Color> for gtk::gdk::RGBA {
     }
 }
 
+impl StaticVariantType for Color {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        <(f64, f64, f64, f64)>::static_variant_type()
+    }
+}
+
+// Colors are passed to actions as their red, green, blue and alpha components, so that
+// neither precision nor colors outside of the sRGB gamut are lost.
+impl ToVariant for Color {
+    fn to_variant(&self) -> Variant {
+        (
+            self.color.red as f64,
+            self.color.green as f64,
+            self.color.blue as f64,
+            self.alpha as f64,
+        )
+            .to_variant()
+    }
+}
+
+impl FromVariant for Color {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        let (red, green, blue, alpha) = variant.get::<(f64, f64, f64, f64)>()?;
+        Some(Self(palette::Srgba::new(
+            red as f32,
+            green as f32,
+            blue as f32,
+            alpha as f32,
+        )))
+    }
+}
+
 impl FromStr for Color {
     type Err = ColorError;
 
@@ -161,3 +413,36 @@ impl fmt::Display for ColorError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use palette::{IntoColor, Luv};
+
+    use super::*;
+    use crate::colors::illuminant::{Illuminant, Observer};
+
+    #[test]
+    fn converts_to_luv() {
+        let red = Color::rgb(255, 0, 0);
+        let luv = red.to_luv(ReferenceWhite::srgb());
+        let expected: Luv = red.color.into_color();
+
+        assert!((luv.l - expected.l).abs() < 1e-2);
+        assert!((luv.u - expected.u).abs() < 1e-2);
+        assert!((luv.v - expected.v).abs() < 1e-2);
+
+        let back = Color::from_luv(luv, ReferenceWhite::srgb());
+        assert_eq!(red.rgba8(), back.rgba8());
+    }
+
+    #[test]
+    fn converts_white_to_luv_for_any_white_point() {
+        let white = Color::rgb(255, 255, 255);
+        for illuminant in [Illuminant::A, Illuminant::D50, Illuminant::F11] {
+            let luv = white.to_luv(ReferenceWhite::new(illuminant, Observer::Degree10));
+            assert!((luv.l - 100.0).abs() < 1e-2);
+            assert!(luv.u.abs() < 1e-2);
+            assert!(luv.v.abs() < 1e-2);
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
-use crate::colors::color::Color;
+use gettextrs::pgettext;
+use palette::convert::IntoColorUnclamped;
+
+use crate::colors::{color::Color, user_palette};
 
 // generated color maps from build.rs
 include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
@@ -20,38 +23,293 @@ pub enum ColorNameSources {
     /// Named colors from the xkcd color survey.
     #[flags_value(name = "xkcd", nick = "xkcd")]
     Xkcd = 8,
+    /// The centroid colors of the 267 categories of the [ISCC-NBS system](https://en.wikipedia.org/wiki/ISCC%E2%80%93NBS_system),
+    /// which name any color that has no closer name in the other palettes.
+    ///
+    /// Colors are named after the closest centroid, which only approximates the category
+    /// they fall into, as the categories are bounded by regions of the Munsell system instead.
+    #[flags_value(name = "ISCC-NBS", nick = "iscc-nbs")]
+    IsccNbs = 16,
+}
+
+/// The palette a name is from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NameSource {
+    Bundled(ColorNameSources),
+    /// A palette from the user data directory, with its identifier.
+    User(&'static str),
+}
+
+impl NameSource {
+    /// The name of the palette, as shown in the preferences.
+    pub fn label(&self) -> String {
+        match self {
+            NameSource::Bundled(ColorNameSources::Html) => pgettext(
+                "Name of the basic color keyword set from https://www.w3.org/TR/css-color-3/#html4",
+                "Basic",
+            ),
+            NameSource::Bundled(ColorNameSources::Svg) => pgettext(
+                "Name of the extended color keyword set from https://www.w3.org/TR/css-color-3/#svg-color",
+                "Extended",
+            ),
+            NameSource::Bundled(ColorNameSources::Gnome) => pgettext(
+                "Name of the color set from the GNOME color palette (https://developer.gnome.org/hig/reference/palette.html)",
+                "GNOME Color Palette",
+            ),
+            NameSource::Bundled(ColorNameSources::IsccNbs) => pgettext(
+                "Name of the ISCC-NBS system of color designations",
+                "ISCC-NBS",
+            ),
+            NameSource::Bundled(_) => pgettext(
+                "Name of the color set from the xkcd color survey",
+                "xkcd Color Survey",
+            ),
+            NameSource::User(id) => id.to_string(),
+        }
+    }
+}
+
+/// One of the names of a color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alias {
+    pub name: &'static str,
+    pub source: NameSource,
 }
 
 /// Returns the corresponding name for a given [`Color`].
 ///
-/// The color is searched in all the enabled palettes, in the order they are listed in.
-/// If none is found [`None`] is returned.
-pub fn name(color: Color, sources: ColorNameSources) -> Option<String> {
+/// This is the first of its [`aliases`], if there are any.
+pub fn name(color: Color, sources: ColorNameSources, user_sources: &[String]) -> Option<String> {
+    aliases(color, sources, user_sources)
+        .first()
+        .map(|alias| alias.name.to_owned())
+}
+
+/// Returns all names of the given [`Color`], together with the palette they are from.
+///
+/// The color is searched in the enabled user palettes in the order of their priority first,
+/// followed by all the enabled bundled palettes, in the order they are listed in.
+/// Within a palette the names keep the order of its file. Names found in multiple palettes
+/// are only listed for the first one.
+pub fn aliases(color: Color, sources: ColorNameSources, user_sources: &[String]) -> Vec<Alias> {
     let hex = color.hex().to_ascii_lowercase();
 
+    let user_aliases = user_sources
+        .iter()
+        .filter_map(|id| user_palette::get(id))
+        .flat_map(|palette| {
+            palette.aliases(&hex).iter().map(|name| Alias {
+                name,
+                source: NameSource::User(&palette.id),
+            })
+        });
+
     let palettes = [
         (ColorNameSources::Html, &BASIC_VALUES),
         (ColorNameSources::Svg, &SVG_VALUES),
         (ColorNameSources::Gnome, &GNOME_VALUES),
         (ColorNameSources::Xkcd, &XKCD_VALUES),
+        (ColorNameSources::IsccNbs, &ISCC_NBS_VALUES),
     ];
-
-    palettes
+    let bundled_aliases = palettes
         .iter()
         .filter(|&&(flag, _)| sources.contains(flag))
-        .find_map(|&(_, palette)| palette.get(&hex).map(|val| val.to_string()))
+        .filter_map(|&(flag, palette)| Some((flag, *palette.get(&hex)?)))
+        .flat_map(|(flag, names)| {
+            names.iter().map(move |name| Alias {
+                name,
+                source: NameSource::Bundled(flag),
+            })
+        });
+
+    let mut aliases: Vec<Alias> = Vec::new();
+    for alias in user_aliases.chain(bundled_aliases) {
+        if !aliases
+            .iter()
+            .any(|other| other.name.eq_ignore_ascii_case(alias.name))
+        {
+            aliases.push(alias);
+        }
+    }
+    aliases
+}
+
+/// A named color of a palette, with its coordinates in Oklab precomputed by build.rs.
+///
+/// Each palette is stored as an implicit k-d tree, in which the median of a slice is the root of the
+/// subtrees before and after it. The slices are split by lightness, a and b in turn.
+/// User palettes use the same layout with owned names, see [`build_tree`].
+#[derive(Debug)]
+pub(super) struct NamedColor<N = &'static str> {
+    pub(super) name: N,
+    pub(super) rgb: [u8; 3],
+    pub(super) oklab: [f32; 3],
+}
+
+impl NamedColor<String> {
+    pub(super) fn new(name: String, color: Color) -> Self {
+        let [red, green, blue, _] = color.rgba8();
+        let oklab: palette::Oklab = color.color.into_color_unclamped();
+        Self {
+            name,
+            rgb: [red, green, blue],
+            oklab: [oklab.l, oklab.a, oklab.b],
+        }
+    }
+}
+
+/// Orders the colors as an implicit k-d tree, the same way build.rs does for the bundled palettes.
+pub(super) fn build_tree<N>(colors: &mut [NamedColor<N>], depth: usize) {
+    if colors.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 3;
+    colors.sort_by(|a, b| a.oklab[axis].total_cmp(&b.oklab[axis]));
+    let median = colors.len() / 2;
+    let (left, right) = colors.split_at_mut(median);
+    build_tree(left, depth + 1);
+    build_tree(&mut right[1..], depth + 1);
+}
+
+/// The name of the closest color in the enabled palettes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NameMatch {
+    pub name: &'static str,
+    /// The named color, which is only the same as the searched one for exact matches.
+    pub color: Color,
+    /// The perceptual difference to the named color, as the euclidean distance in Oklab scaled by 100.
+    ///
+    /// Differences of about 2 are just noticeable side by side.
+    pub distance: f32,
+}
+
+impl NameMatch {
+    /// Whether the name belongs to exactly the same color.
+    pub fn is_exact(&self) -> bool {
+        self.distance < f32::EPSILON
+    }
+}
+
+/// Returns the name of the perceptually closest color in the enabled palettes.
+///
+/// The alpha value is ignored, so translucent colors are named by their opaque color.
+/// If the closest color differs by more than the `tolerance`, the ISCC-NBS category
+/// with the closest centroid color is returned instead, or [`None`] if the ISCC-NBS names are disabled.
+/// This is only an approximation, as the Munsell regions of the categories are not looked up.
+/// On equal distances the user palettes are preferred in the order of their priority,
+/// followed by the bundled palettes in the order they are listed in.
+///
+/// The palettes are searched using their precomputed k-d trees, without allocating.
+pub fn nearest(
+    color: Color,
+    sources: ColorNameSources,
+    user_sources: &[String],
+    tolerance: f32,
+) -> Option<NameMatch> {
+    let trees: [(ColorNameSources, &'static [NamedColor]); 4] = [
+        (ColorNameSources::Html, &BASIC_TREE),
+        (ColorNameSources::Svg, &SVG_TREE),
+        (ColorNameSources::Gnome, &GNOME_TREE),
+        (ColorNameSources::Xkcd, &XKCD_TREE),
+    ];
+
+    // out of gamut colors are never exact matches, even though their clamped 8-bit values might be
+    let [red, green, blue, _] = color.rgba8();
+    let rgb = color.is_in_srgb_gamut().then_some([red, green, blue]);
+    let oklab: palette::Oklab = color.color.into_color_unclamped();
+    let target = [oklab.l, oklab.a, oklab.b];
+
+    // squared distances are compared, the square root is only taken for the result
+    let mut best = None;
+    for palette in user_sources.iter().filter_map(|id| user_palette::get(id)) {
+        search(palette.tree(), rgb, target, 0, &mut best);
+    }
+    for (_, tree) in trees.iter().filter(|(flag, _)| sources.contains(*flag)) {
+        search(tree, rgb, target, 0, &mut best);
+    }
+
+    let to_match =
+        |(name, [red, green, blue], squared_distance): (&'static str, [u8; 3], f32)| NameMatch {
+            name,
+            color: Color::rgb(red, green, blue),
+            distance: squared_distance.sqrt() * 100.0,
+        };
+    match best.map(to_match) {
+        Some(found) if found.distance <= tolerance => Some(found),
+        // every color belongs to one of the categories, so they do not need a tolerance
+        _ if sources.contains(ColorNameSources::IsccNbs) => {
+            let mut category = None;
+            search(&ISCC_NBS_TREE, rgb, target, 0, &mut category);
+            category.map(to_match)
+        }
+        _ => None,
+    }
+}
+
+/// Searches the k-d tree for a color closer than the `best` one found so far.
+fn search<N: AsRef<str>>(
+    tree: &'static [NamedColor<N>],
+    rgb: Option<[u8; 3]>,
+    target: [f32; 3],
+    depth: usize,
+    best: &mut Option<(&'static str, [u8; 3], f32)>,
+) {
+    if tree.is_empty() {
+        return;
+    }
+
+    let median = tree.len() / 2;
+    let named = &tree[median];
+    // colors with the same 8-bit values are exact matches, regardless of rounding errors
+    let squared_distance = if Some(named.rgb) == rgb {
+        0.0
+    } else {
+        named
+            .oklab
+            .iter()
+            .zip(target)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum()
+    };
+    if best.map_or(true, |(_, _, best)| squared_distance < best) {
+        *best = Some((named.name.as_ref(), named.rgb, squared_distance));
+    }
+
+    let axis = depth % 3;
+    let offset = target[axis] - named.oklab[axis];
+    let (near, far) = if offset < 0.0 {
+        (&tree[..median], &tree[median + 1..])
+    } else {
+        (&tree[median + 1..], &tree[..median])
+    };
+    search(near, rgb, target, depth + 1, best);
+    // the other side can only contain a closer color, if it is closer than the splitting plane
+    if best.map_or(true, |(_, _, best)| offset * offset < best) {
+        search(far, rgb, target, depth + 1, best);
+    }
 }
 
 /// Returns the corresponding [`Color`] for a given name.
 ///
-/// The color is searched in all the enabled palettes, in the order they are listed in.
+/// The name is searched in the enabled user palettes in the order of their priority first,
+/// followed by all the enabled bundled palettes, in the order they are listed in.
 /// If none is found [`None`] is returned.
-pub fn color(name: &str, sources: ColorNameSources) -> Option<Color> {
+pub fn color(name: &str, sources: ColorNameSources, user_sources: &[String]) -> Option<Color> {
+    let user_color = user_sources
+        .iter()
+        .filter_map(|id| user_palette::get(id))
+        .find_map(|palette| palette.color(name));
+    if user_color.is_some() {
+        return user_color;
+    }
+
     let palettes = [
         (ColorNameSources::Html, &BASIC),
         (ColorNameSources::Svg, &SVG),
         (ColorNameSources::Gnome, &GNOME),
         (ColorNameSources::Xkcd, &XKCD),
+        (ColorNameSources::IsccNbs, &ISCC_NBS),
     ];
 
     palettes
@@ -60,3 +318,117 @@ pub fn color(name: &str, sources: ColorNameSources) -> Option<Color> {
         .filter_map(|&(_, palette)| palette.get(&name.to_ascii_lowercase()))
         .find_map(|val| Color::from_str(val).ok())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_the_nearest_name() {
+        let exact = nearest(Color::rgb(255, 0, 0), ColorNameSources::Html, &[], 10.0).unwrap();
+        assert_eq!("red", exact.name);
+        assert!(exact.is_exact());
+
+        let close = nearest(Color::rgb(250, 5, 3), ColorNameSources::Html, &[], 10.0).unwrap();
+        assert_eq!("red", close.name);
+        assert!(!close.is_exact());
+        assert!(close.distance > 0.0 && close.distance < 2.0);
+
+        assert_eq!(
+            None,
+            nearest(Color::rgb(250, 5, 3), ColorNameSources::Html, &[], 0.5)
+        );
+    }
+
+    #[test]
+    fn it_names_translucent_colors() {
+        let found = nearest(
+            Color::rgba(255, 0, 0, 128),
+            ColorNameSources::Html,
+            &[],
+            0.0,
+        )
+        .unwrap();
+        assert_eq!("red", found.name);
+    }
+
+    #[test]
+    fn it_names_any_color_with_iscc_nbs() {
+        let sources = ColorNameSources::Html | ColorNameSources::IsccNbs;
+
+        let found = nearest(Color::rgb(255, 0, 0), sources, &[], 10.0).unwrap();
+        assert_eq!("red", found.name);
+
+        let found = nearest(Color::rgb(78, 81, 128), sources, &[], 10.0).unwrap();
+        assert_eq!("moderate purplish blue", found.name);
+        assert!(found.is_exact());
+
+        let found = nearest(Color::rgb(80, 85, 125), sources, &[], 0.0).unwrap();
+        assert_eq!("moderate purplish blue", found.name);
+        assert!(!found.is_exact());
+    }
+
+    #[test]
+    fn it_lists_all_aliases() {
+        let aliases = aliases(
+            Color::rgb(0, 255, 255),
+            ColorNameSources::Html | ColorNameSources::Svg,
+            &[],
+        );
+        assert_eq!(
+            vec![
+                Alias {
+                    name: "aqua",
+                    source: NameSource::Bundled(ColorNameSources::Html)
+                },
+                Alias {
+                    name: "cyan",
+                    source: NameSource::Bundled(ColorNameSources::Svg)
+                },
+            ],
+            aliases
+        );
+
+        assert_eq!(
+            Some("gray".to_owned()),
+            name(Color::rgb(128, 128, 128), ColorNameSources::Html, &[])
+        );
+    }
+
+    #[test]
+    fn it_searches_the_trees_like_a_linear_scan() {
+        let sources = ColorNameSources::all();
+        let all = BASIC_TREE
+            .iter()
+            .chain(&SVG_TREE)
+            .chain(&GNOME_TREE)
+            .chain(&XKCD_TREE)
+            .collect::<Vec<_>>();
+
+        // a grid through the whole sRGB cube, including its corners
+        let steps = (0..=255).step_by(15);
+        for red in steps.clone() {
+            for green in steps.clone() {
+                for blue in steps.clone() {
+                    let color = Color::rgb(red, green, blue);
+                    let oklab: palette::Oklab = color.color.into_color_unclamped();
+                    let closest = all
+                        .iter()
+                        .map(|named| {
+                            let [l, a, b] = named.oklab;
+                            let distance = ((l - oklab.l).powi(2)
+                                + (a - oklab.a).powi(2)
+                                + (b - oklab.b).powi(2))
+                            .sqrt();
+                            distance * 100.0
+                        })
+                        .min_by(f32::total_cmp)
+                        .unwrap();
+
+                    let found = nearest(color, sources, &[], f32::INFINITY).unwrap();
+                    assert!((found.distance - closest).abs() < 1e-3, "{color:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use palette::{convert::IntoColorUnclamped, LinSrgba, Srgba, WithAlpha, Xyz};
+
+use super::{color::Color, illuminant::ReferenceWhite, rgb_space::RgbSpace};
+
+/// The syntax used for a CSS color function.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum CssSyntax {
+    /// The comma separated syntax of CSS Color 3, such as `rgba(46, 52, 64, 0.5)`.
+    #[default]
+    Legacy,
+    /// The space separated syntax of CSS Color 4, such as `rgb(46 52 64 / 0.5)`.
+    Modern,
+}
+
+//Convert from U32. Needed for converting from the settings AdwComboRow, which use indexes for values.
+impl From<u32> for CssSyntax {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Legacy,
+            1 => Self::Modern,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// A predefined color space, that can be used in the CSS `color()` function.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum PredefinedSpace {
+    #[default]
+    Srgb,
+    SrgbLinear,
+    XyzD50,
+    XyzD65,
+    Rgb(RgbSpace),
+}
+
+//Convert from U32. Needed for converting from the settings AdwComboRow, which use indexes for values.
+impl From<u32> for PredefinedSpace {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Srgb,
+            1 => Self::SrgbLinear,
+            2 => Self::Rgb(RgbSpace::DisplayP3),
+            3 => Self::Rgb(RgbSpace::AdobeRgb),
+            4 => Self::Rgb(RgbSpace::ProPhotoRgb),
+            5 => Self::Rgb(RgbSpace::Rec2020),
+            6 => Self::XyzD50,
+            7 => Self::XyzD65,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl PredefinedSpace {
+    /// The identifier of the color space in the CSS `color()` function.
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            PredefinedSpace::Srgb => "srgb",
+            PredefinedSpace::SrgbLinear => "srgb-linear",
+            PredefinedSpace::XyzD50 => "xyz-d50",
+            PredefinedSpace::XyzD65 => "xyz-d65",
+            PredefinedSpace::Rgb(space) => space.css_name(),
+        }
+    }
+
+    /// Returns the three components of the color in this color space, without clamping them.
+    pub fn components(&self, color: Color) -> [f32; 3] {
+        match self {
+            PredefinedSpace::Srgb => [color.red, color.green, color.blue],
+            PredefinedSpace::SrgbLinear => {
+                let linear: palette::LinSrgb = color.color.into_color_unclamped();
+                [linear.red, linear.green, linear.blue]
+            }
+            PredefinedSpace::XyzD50 | PredefinedSpace::XyzD65 => {
+                let xyz = color.to_xyz(self.reference_white());
+                [xyz.x, xyz.y, xyz.z]
+            }
+            PredefinedSpace::Rgb(space) => space.components(color),
+        }
+    }
+
+    /// Creates a color from the three components in this color space.
+    pub fn color(&self, [a, b, c]: [f32; 3], alpha: f32) -> Color {
+        match self {
+            PredefinedSpace::Srgb => Color::from_palette_unclamped(Srgba::new(a, b, c, alpha)),
+            PredefinedSpace::SrgbLinear => {
+                Color::from_palette_unclamped(LinSrgba::new(a, b, c, alpha))
+            }
+            PredefinedSpace::XyzD50 | PredefinedSpace::XyzD65 => {
+                Color::from_xyz(Xyz::new(a, b, c).with_alpha(alpha), self.reference_white())
+            }
+            PredefinedSpace::Rgb(space) => space.color([a, b, c], alpha),
+        }
+    }
+
+    fn reference_white(&self) -> ReferenceWhite {
+        match self {
+            PredefinedSpace::XyzD50 => ReferenceWhite::d50(),
+            _ => ReferenceWhite::srgb(),
+        }
+    }
+}
+
+/// The syntax used for each of the CSS color functions, which have both a legacy and a modern syntax.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CssStyle {
+    pub rgb: CssSyntax,
+    pub hsl: CssSyntax,
+    pub hwb: CssSyntax,
+    /// The color space used for the `color()` function.
+    pub color_space: PredefinedSpace,
+}
@@ -0,0 +1,140 @@
+use std::{fmt, ops::Range};
+
+use gettextrs::gettext;
+
+/// What was expected instead of a part of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expectation {
+    HexDigits,
+    AlphaValue,
+    Percentage,
+    Hue,
+    /// A percentage of the CSS color functions, which can also be a number between 0 and 100.
+    CssPercentage,
+    RgbValue,
+    ClosingParenthesis,
+    ColorName,
+    /// A value like the given example, when nothing more specific is known.
+    Example(String),
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Expectation::HexDigits => gettext("two hex digits"),
+            Expectation::AlphaValue => gettext("an alpha value between 0 and 1 or a percentage"),
+            Expectation::Percentage => gettext("a percentage, such as “50%”"),
+            Expectation::Hue => gettext("a hue, such as “220” or “220deg”"),
+            Expectation::CssPercentage => gettext("“%” or a number between 0 and 100"),
+            Expectation::RgbValue => gettext("a number between 0 and 255 or a percentage"),
+            Expectation::ClosingParenthesis => gettext("“)”"),
+            Expectation::ColorName => gettext("a color name"),
+            Expectation::Example(example) => gettext("a value like “{}”").replace("{}", example),
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// What is wrong with a part of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// Something else was expected, such as a hue.
+    Expected(Expectation),
+    /// The value is outside of its range and has been clamped.
+    Clamped,
+    /// The input follows a complete color and has been ignored.
+    Ignored,
+}
+
+/// Explains what is wrong with a part of the input, so it can be shown next to the entered value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The byte range of the part in the input, which is empty at the end of the input.
+    pub span: Range<usize>,
+    /// The text of the part in the input.
+    pub text: String,
+    pub kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic for the given range of the input.
+    pub fn new(input: &str, span: Range<usize>, kind: DiagnosticKind) -> Self {
+        Self {
+            text: input[span.clone()].to_owned(),
+            span,
+            kind,
+        }
+    }
+
+    /// Creates a diagnostic for the token starting at the given byte offset,
+    /// which ends before the next whitespace, separator or parenthesis.
+    pub fn at_token(input: &str, offset: usize, kind: DiagnosticKind) -> Self {
+        let rest = &input[offset..];
+        let is_boundary =
+            |char: char| char.is_whitespace() || matches!(char, ',' | '|' | '/' | '(' | ')');
+        let length = match rest.find(is_boundary) {
+            // always include at least the first character, such as an unexpected separator
+            Some(0) => rest.chars().next().map_or(0, char::len_utf8),
+            Some(length) => length,
+            None => rest.len(),
+        };
+        Self::new(input, offset..offset + length, kind)
+    }
+
+    /// Whether the input could not be parsed, instead of being parsed with some adjustments.
+    pub fn is_error(&self) -> bool {
+        matches!(self.kind, DiagnosticKind::Expected(_))
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match &self.kind {
+            DiagnosticKind::Expected(expected) if self.text.is_empty() => {
+                gettext("Expected {} at the end").replace("{}", &expected.to_string())
+            }
+            DiagnosticKind::Expected(expected) => {
+                gettext("Expected {expected} instead of “{text}”")
+                    .replace("{expected}", &expected.to_string())
+                    .replace("{text}", &self.text)
+            }
+            DiagnosticKind::Clamped => {
+                gettext("“{}” is out of range and has been clamped").replace("{}", &self.text)
+            }
+            DiagnosticKind::Ignored => gettext("“{}” has been ignored").replace("{}", &self.text),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_spans_the_token() {
+        let input = "rgb(46, 5x2, 64)";
+        let diagnostic = Diagnostic::at_token(input, 9, DiagnosticKind::Ignored);
+        assert_eq!(9..11, diagnostic.span);
+        assert_eq!("x2", diagnostic.text);
+
+        let diagnostic = Diagnostic::at_token(input, 11, DiagnosticKind::Ignored);
+        assert_eq!(",", diagnostic.text);
+
+        let diagnostic = Diagnostic::at_token(input, input.len(), DiagnosticKind::Ignored);
+        assert!(diagnostic.span.is_empty());
+    }
+
+    #[test]
+    fn it_describes_the_expectation() {
+        let diagnostic = Diagnostic::new(
+            "hsl(220, 16x, 22%)",
+            9..12,
+            DiagnosticKind::Expected(Expectation::CssPercentage),
+        );
+        assert_eq!(
+            "Expected “%” or a number between 0 and 100 instead of “16x”",
+            diagnostic.to_string()
+        );
+    }
+}
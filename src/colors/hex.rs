@@ -0,0 +1,122 @@
+use super::{color::Color, position::AlphaPosition};
+
+/// The prefix shown in front of hex codes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum HexPrefix {
+    /// The `#` used by CSS and most design tools.
+    #[default]
+    Hash,
+    /// The `0x` used by integer literals in most programming languages.
+    ZeroX,
+}
+
+//Convert from U32. Needed for converting from the settings AdwComboRow, which use indexes for values.
+impl From<u32> for HexPrefix {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Hash,
+            1 => Self::ZeroX,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// How hex codes are formatted.
+///
+/// Defaults to the long form in uppercase with a leading `#`, such as `#2E3440`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HexStyle {
+    /// Whether to use one digit per component, such as `#FFF`, when no information is lost.
+    pub short_form: bool,
+    pub uppercase: bool,
+    pub prefix: HexPrefix,
+}
+
+impl Default for HexStyle {
+    fn default() -> Self {
+        Self {
+            short_form: false,
+            uppercase: true,
+            prefix: HexPrefix::default(),
+        }
+    }
+}
+
+impl HexStyle {
+    /// Formats the color as a hex code, with the alpha value at the given position.
+    pub fn format(&self, color: Color, alpha_position: AlphaPosition) -> String {
+        let [red, green, blue, alpha] = color.rgba8();
+        let values = match alpha_position {
+            AlphaPosition::None => vec![red, green, blue],
+            AlphaPosition::End => vec![red, green, blue, alpha],
+            AlphaPosition::Start => vec![alpha, red, green, blue],
+        };
+
+        // a value can only be written with a single digit if both of its digits are the same
+        let is_short = self.short_form && values.iter().all(|value| value >> 4 == value & 0xF);
+        let digits = values
+            .iter()
+            .map(|value| {
+                if is_short {
+                    format!("{:X}", value & 0xF)
+                } else {
+                    format!("{:02X}", value)
+                }
+            })
+            .collect::<String>();
+
+        let prefix = match self.prefix {
+            HexPrefix::Hash => "#",
+            HexPrefix::ZeroX => "0x",
+        };
+        if self.uppercase {
+            format!("{}{}", prefix, digits)
+        } else {
+            format!("{}{}", prefix, digits.to_lowercase())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_uses_the_short_form_only_when_lossless() {
+        let style = HexStyle {
+            short_form: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "#F0A",
+            style.format(Color::rgb(255, 0, 170), AlphaPosition::None)
+        );
+        assert_eq!(
+            "#F0A8",
+            style.format(Color::rgba(255, 0, 170, 136), AlphaPosition::End)
+        );
+        assert_eq!(
+            "#8F0A",
+            style.format(Color::rgba(255, 0, 170, 136), AlphaPosition::Start)
+        );
+        assert_eq!(
+            "#2E3440",
+            style.format(Color::rgb(46, 52, 64), AlphaPosition::None)
+        );
+    }
+
+    #[test]
+    fn it_uses_the_case_and_prefix() {
+        let style = HexStyle {
+            short_form: false,
+            uppercase: false,
+            prefix: HexPrefix::ZeroX,
+        };
+
+        assert_eq!(
+            "0x2e3440",
+            style.format(Color::rgb(46, 52, 64), AlphaPosition::None)
+        );
+    }
+}
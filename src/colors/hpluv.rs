@@ -0,0 +1,155 @@
+use std::marker::PhantomData;
+
+use palette::{
+    convert::FromColorUnclamped,
+    white_point::{WhitePoint, D65},
+    Clamp, Lchuv, LuvHue, WithAlpha,
+};
+
+/// HPLuv with an alpha component.
+///
+/// HPLuv is a variant of HSLuv, that only contains pastel colors. The saturation is relative
+/// to the largest chroma that is available for every hue at a given lightness, so unlike HSLuv
+/// the chroma does not change with the hue. Saturated colors have a saturation larger than 100%.
+///
+/// Based on <https://www.hsluv.org/math/>
+#[derive(Debug, FromColorUnclamped, WithAlpha)]
+#[palette(skip_derives(Lchuv), white_point = "Wp")]
+pub struct Hpluv<Wp = D65> {
+    /// The hue of the color, in degrees.
+    pub hue: LuvHue<f32>,
+    /// The saturation of the color, where 0.0 is gray and 100.0 is the largest
+    /// chroma available for all hues.
+    pub saturation: f32,
+    /// The lightness of the color, where 0.0 is black and 100.0 is white.
+    pub l: f32,
+    /// The white point associated with the color's illuminant and observer.
+    /// D65 for 2 degree observer is used by default.
+    #[palette(unsafe_zero_sized)]
+    pub white_point: PhantomData<Wp>,
+}
+
+impl<Wp> Hpluv<Wp> {
+    /// Create a HPLuv color
+    pub fn new(hue: impl Into<LuvHue<f32>>, saturation: f32, l: f32) -> Self {
+        Self {
+            hue: hue.into(),
+            saturation,
+            l,
+            white_point: PhantomData,
+        }
+    }
+}
+
+/// The matrix to convert XYZ values into linear sRGB values.
+const XYZ_TO_LINEAR_SRGB: [[f64; 3]; 3] = [
+    [3.240969941904521, -1.537383177570093, -0.498610760293],
+    [-0.96924363628087, 1.87596750150772, 0.041555057407175],
+    [0.055630079696993, -0.20397695888897, 1.056971514242878],
+];
+const KAPPA: f64 = 903.2962962;
+const EPSILON: f64 = 0.0088564516;
+
+/// Returns the largest chroma, which is within the sRGB gamut for every hue at the given lightness.
+///
+/// The gamut is a polygon in the uv plane, formed by six lines. The largest chroma available
+/// for every hue is the distance from the origin to the closest of these lines.
+fn max_safe_chroma(l: f32) -> f32 {
+    let l = l as f64;
+    let sub1 = (l + 16.0).powi(3) / 1560896.0;
+    let sub2 = if sub1 > EPSILON { sub1 } else { l / KAPPA };
+
+    let mut min_distance = f64::MAX;
+    for [m1, m2, m3] in XYZ_TO_LINEAR_SRGB {
+        for t in [0.0, 1.0] {
+            let top1 = (284517.0 * m1 - 94839.0 * m3) * sub2;
+            let top2 =
+                (838422.0 * m3 + 769860.0 * m2 + 731718.0 * m1) * l * sub2 - 769860.0 * t * l;
+            let bottom = (632260.0 * m3 - 126452.0 * m2) * sub2 + 126452.0 * t;
+
+            let (slope, intercept) = (top1 / bottom, top2 / bottom);
+            min_distance = min_distance.min(intercept.abs() / (slope * slope + 1.0).sqrt());
+        }
+    }
+
+    min_distance as f32
+}
+
+/// Whether the lightness is black or white, which do not have a chroma.
+fn is_achromatic(l: f32) -> bool {
+    !(1e-5..=99.99999).contains(&l)
+}
+
+impl<Wp> FromColorUnclamped<Hpluv<Wp>> for Hpluv<Wp> {
+    fn from_color_unclamped(color: Hpluv<Wp>) -> Hpluv<Wp> {
+        color
+    }
+}
+
+impl<Wp> FromColorUnclamped<Lchuv<Wp, f32>> for Hpluv<Wp>
+where
+    Wp: WhitePoint<f32>,
+{
+    fn from_color_unclamped(color: Lchuv<Wp, f32>) -> Self {
+        let saturation = if is_achromatic(color.l) {
+            0.0
+        } else {
+            color.chroma / max_safe_chroma(color.l) * 100.0
+        };
+
+        Self::new(color.hue, saturation, color.l)
+    }
+}
+
+impl<Wp> FromColorUnclamped<Hpluv<Wp>> for Lchuv<Wp, f32>
+where
+    Wp: WhitePoint<f32>,
+{
+    fn from_color_unclamped(color: Hpluv<Wp>) -> Self {
+        let chroma = if is_achromatic(color.l) {
+            0.0
+        } else {
+            max_safe_chroma(color.l) / 100.0 * color.saturation
+        };
+
+        Lchuv::new(color.l, chroma, color.hue)
+    }
+}
+
+impl<Wp> Clamp for Hpluv<Wp> {
+    fn clamp(self) -> Self {
+        Hpluv {
+            hue: self.hue,
+            saturation: self.saturation.max(0.0),
+            l: self.l.clamp(0.0, 100.0),
+            white_point: self.white_point,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use palette::{convert::IntoColorUnclamped, Srgb};
+
+    use super::*;
+
+    #[test]
+    fn converts_from_srgb() {
+        let hpluv: Hpluv =
+            Srgb::new(46.0 / 255.0, 52.0 / 255.0, 64.0 / 255.0).into_color_unclamped();
+        assert!((hpluv.hue.into_positive_degrees() - 250.718).abs() < 1e-2);
+        assert!((hpluv.saturation - 57.172).abs() < 1e-2);
+        assert!((hpluv.l - 21.605).abs() < 1e-2);
+    }
+
+    #[test]
+    fn keeps_white_achromatic() {
+        let hpluv: Hpluv = Srgb::new(1.0, 1.0, 1.0).into_color_unclamped();
+        assert_eq!(0.0, hpluv.saturation);
+
+        let srgb: Srgb = Hpluv::<D65>::new(120.0, 50.0, 100.0).into_color_unclamped();
+        assert!((srgb.red - 1.0).abs() < 1e-4);
+        assert!((srgb.green - 1.0).abs() < 1e-4);
+        assert!((srgb.blue - 1.0).abs() < 1e-4);
+    }
+}
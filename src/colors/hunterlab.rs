@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use palette::{
     convert::FromColorUnclamped,
-    white_point::{WhitePoint, D65},
+    white_point::{Any, WhitePoint, D65},
     Clamp, WithAlpha, Xyz,
 };
 
@@ -12,10 +12,11 @@ use palette::{
 #[derive(Debug, FromColorUnclamped, WithAlpha)]
 #[palette(skip_derives(Xyz))]
 pub struct HunterLab<Wp = D65> {
+    /// The lightness of the color, where 0.0 is black and 100.0 is white.
     pub l: f32,
-    /// The amount of magenta ink, where 0.0 is no magenta ink and 1.0 is the maximum amount.
+    /// The position between red and green, where negative values are green and positive values are red.
     pub a: f32,
-    /// The amount of yellow ink, where 0.0 is no yellow ink and 1.0 is the maximum amount.
+    /// The position between yellow and blue, where negative values are blue and positive values are yellow.
     pub b: f32,
     /// The white point associated with the color's illuminant and observer.
     /// D65 for 2 degree observer is used by default.
@@ -33,6 +34,39 @@ impl<Wp> HunterLab<Wp> {
             white_point: PhantomData,
         }
     }
+
+    /// Convert from XYZ, relative to the given reference white.
+    ///
+    /// Unlike the [`FromColorUnclamped`] implementation, this allows choosing the white point at runtime.
+    pub fn from_xyz(color: Xyz<Wp, f32>, white: Xyz<Any, f32>) -> Self {
+        let ka = (175.0 / 198.04) * (white.x + white.y) * 100.0;
+        let kb = (70.0 / 218.11) * (white.y + white.z) * 100.0;
+
+        let l = 100.0 * f32::sqrt(color.y / white.y);
+        let a = ka * (((color.x / white.x) - (color.y / white.y)) / f32::sqrt(color.y / white.y));
+        let b = kb * (((color.y / white.y) - (color.z / white.z)) / f32::sqrt(color.y / white.y));
+
+        Self {
+            l,
+            a: if a.is_nan() { 0.0 } else { a },
+            b: if b.is_nan() { 0.0 } else { b },
+            white_point: PhantomData,
+        }
+    }
+
+    /// Convert into XYZ, relative to the given reference white.
+    ///
+    /// Unlike the [`FromColorUnclamped`] implementation, this allows choosing the white point at runtime.
+    pub fn into_xyz(self, white: Xyz<Any, f32>) -> Xyz<Wp, f32> {
+        let ka = (175.0 / 198.04) * (white.y + white.x) * 100.0;
+        let kb = (70.0 / 218.11) * (white.y + white.z) * 100.0;
+
+        let y = (self.l / 100.0).powi(2) * white.y;
+        let x = (self.a / ka * (y / white.y).sqrt() + (y / white.y)) * white.x;
+        let z = -(self.b / kb * (y / white.y).sqrt() - (y / white.y)) * white.z;
+
+        Xyz::new(x, y, z)
+    }
 }
 
 impl FromColorUnclamped<HunterLab> for HunterLab {
@@ -46,21 +80,7 @@ where
     Wp: WhitePoint<f32>,
 {
     fn from_color_unclamped(color: Xyz<Wp, f32>) -> Self {
-        let wp = Wp::get_xyz();
-
-        let ka = (175.0 / 198.04) * (wp.x + wp.y);
-        let kb = (70.0 / 218.11) * (wp.y + wp.z);
-
-        let l = 100.0 * f32::sqrt(color.y / wp.y);
-        let a = ka * (((color.x / wp.x) - (color.y / wp.y)) / f32::sqrt(color.y / wp.y));
-        let b = kb * (((color.y / wp.y) - (color.z / wp.z)) / f32::sqrt(color.y / wp.y));
-
-        Self {
-            l,
-            a: if a.is_nan() { 0.0 } else { a },
-            b: if b.is_nan() { 0.0 } else { b },
-            white_point: PhantomData::<Wp>,
-        }
+        Self::from_xyz(color, Wp::get_xyz())
     }
 }
 
@@ -69,15 +89,7 @@ where
     Wp: WhitePoint<f32>,
 {
     fn from_color_unclamped(color: HunterLab<Wp>) -> Self {
-        let wp = Wp::get_xyz();
-        let ka = (175.0 / 198.04) * (wp.y + wp.x);
-        let kb = (70.0 / 218.11) * (wp.y + wp.z);
-
-        let y = ((color.l / wp.y).powi(2)) * 100.0;
-        let x = (color.a / ka * (y / wp.y).sqrt() + (y / wp.y)) * wp.x;
-        let z = -(color.b / kb * (y / wp.y).sqrt() - (y / wp.y)) * wp.z;
-
-        palette::Xyz::new(x, y, z)
+        color.into_xyz(Wp::get_xyz())
     }
 }
 
@@ -0,0 +1,128 @@
+use palette::{convert::FromColorUnclamped, white_point::D65, Clamp, WithAlpha, Xyz};
+
+use super::{
+    matrix::{self, Matrix3},
+    pq,
+    rgb_space::RgbSpace,
+};
+
+/// ICtCp with an alpha component.
+///
+/// ICtCp is the color representation of ITU-R BT.2100 for HDR content, using the
+/// PQ transfer function. It is based on absolute luminance, so the luminance of the SDR
+/// white has to be known to convert relative colors.
+///
+/// Based on <https://www.itu.int/rec/R-REC-BT.2100>
+#[derive(Debug, FromColorUnclamped, WithAlpha)]
+#[palette(skip_derives(Xyz), white_point = "D65")]
+pub struct Ictcp {
+    /// The intensity of the color, where 0.0 is black and 1.0 is the peak luminance of 10000 cd/m².
+    pub i: f32,
+    /// The position between yellow and blue, where negative values are yellow and positive values are blue.
+    pub ct: f32,
+    /// The position between green and red, where negative values are green and positive values are red.
+    pub cp: f32,
+}
+
+const RGB_TO_LMS: Matrix3 = [
+    [1688.0 / 4096.0, 2146.0 / 4096.0, 262.0 / 4096.0],
+    [683.0 / 4096.0, 2951.0 / 4096.0, 462.0 / 4096.0],
+    [99.0 / 4096.0, 309.0 / 4096.0, 3688.0 / 4096.0],
+];
+const LMS_TO_ICTCP: Matrix3 = [
+    [0.5, 0.5, 0.0],
+    [6610.0 / 4096.0, -13613.0 / 4096.0, 7003.0 / 4096.0],
+    [17933.0 / 4096.0, -17390.0 / 4096.0, -543.0 / 4096.0],
+];
+
+impl Ictcp {
+    /// Create an ICtCp color
+    pub const fn new(i: f32, ct: f32, cp: f32) -> Self {
+        Self { i, ct, cp }
+    }
+
+    /// Convert from XYZ, where a luminance of 1.0 has the given absolute luminance in cd/m².
+    ///
+    /// Unlike the [`FromColorUnclamped`] implementation, this allows choosing the luminance at runtime.
+    pub fn from_xyz(color: Xyz<D65, f32>, white_luminance: f32) -> Self {
+        let rgb = matrix::multiply(
+            matrix::invert(RgbSpace::Rec2020.rgb_to_xyz_matrix()),
+            [color.x, color.y, color.z],
+        );
+        let lms = matrix::multiply(
+            RGB_TO_LMS,
+            rgb.map(|value| value * white_luminance / pq::PEAK_LUMINANCE),
+        )
+        .map(|value| pq::encode(value, pq::M2));
+
+        let [i, ct, cp] = matrix::multiply(LMS_TO_ICTCP, lms);
+        Self::new(i, ct, cp)
+    }
+
+    /// Convert into XYZ, where a luminance of 1.0 has the given absolute luminance in cd/m².
+    ///
+    /// Unlike the [`FromColorUnclamped`] implementation, this allows choosing the luminance at runtime.
+    pub fn into_xyz(self, white_luminance: f32) -> Xyz<D65, f32> {
+        let lms = matrix::multiply(matrix::invert(LMS_TO_ICTCP), [self.i, self.ct, self.cp])
+            .map(|value| pq::decode(value, pq::M2));
+        let rgb = matrix::multiply(matrix::invert(RGB_TO_LMS), lms)
+            .map(|value| value * pq::PEAK_LUMINANCE / white_luminance);
+
+        let [x, y, z] = matrix::multiply(RgbSpace::Rec2020.rgb_to_xyz_matrix(), rgb);
+        Xyz::new(x, y, z)
+    }
+}
+
+impl FromColorUnclamped<Ictcp> for Ictcp {
+    fn from_color_unclamped(color: Ictcp) -> Ictcp {
+        color
+    }
+}
+
+impl FromColorUnclamped<Xyz<D65, f32>> for Ictcp {
+    fn from_color_unclamped(color: Xyz<D65, f32>) -> Self {
+        Self::from_xyz(color, pq::SDR_WHITE_LUMINANCE)
+    }
+}
+
+impl FromColorUnclamped<Ictcp> for Xyz<D65, f32> {
+    fn from_color_unclamped(color: Ictcp) -> Self {
+        color.into_xyz(pq::SDR_WHITE_LUMINANCE)
+    }
+}
+
+impl Clamp for Ictcp {
+    fn clamp(self) -> Self {
+        Ictcp::new(self.i.clamp(0.0, 1.0), self.ct, self.cp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use palette::{convert::IntoColorUnclamped, Srgb};
+
+    use super::*;
+
+    #[test]
+    fn converts_white() {
+        // the SDR white has the PQ encoded value of its luminance and is achromatic
+        let ictcp: Ictcp = Srgb::new(1.0, 1.0, 1.0).into_color_unclamped();
+        assert!((ictcp.i - 0.5806).abs() < 1e-3, "{ictcp:?}");
+        assert!(ictcp.ct.abs() < 1e-3);
+        assert!(ictcp.cp.abs() < 1e-3);
+    }
+
+    #[test]
+    fn round_trips() {
+        let color = Srgb::new(0.18, 0.62, 0.95);
+        for white_luminance in [80.0, 203.0, 1000.0] {
+            let xyz: Xyz<D65, f32> = color.into_color_unclamped();
+            let srgb: Srgb = Ictcp::from_xyz(xyz, white_luminance)
+                .into_xyz(white_luminance)
+                .into_color_unclamped();
+            assert!((srgb.red - color.red).abs() < 1e-4);
+            assert!((srgb.green - color.green).abs() < 1e-4);
+            assert!((srgb.blue - color.blue).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use palette::{white_point::Any, Xyz};
+
+use super::matrix::{self, Matrix3};
+
+/// A CIE standard illuminant, used as the reference white for CIE color spaces.
+///
+/// Defaults to D65, which is also the white point of sRGB.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Illuminant {
+    /// Incandescent/tungsten light.
+    A,
+    /// Average/north sky daylight, deprecated in favor of D65.
+    C,
+    /// Horizon light, commonly used in printing.
+    D50,
+    /// Mid-morning/mid-afternoon daylight.
+    D55,
+    /// Noon daylight, used by sRGB and most displays.
+    #[default]
+    D65,
+    /// North sky daylight.
+    D75,
+    /// Equal energy.
+    E,
+    /// Cool white fluorescent.
+    F2,
+    /// Broad-band daylight fluorescent.
+    F7,
+    /// Narrow tri-band fluorescent.
+    F11,
+}
+
+//Convert from U32. Needed for converting from the settings AdwComboRow, which use indexes for values.
+impl From<u32> for Illuminant {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::A,
+            1 => Self::C,
+            2 => Self::D50,
+            3 => Self::D55,
+            4 => Self::D65,
+            5 => Self::D75,
+            6 => Self::E,
+            7 => Self::F2,
+            8 => Self::F7,
+            9 => Self::F11,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// The CIE standard observer, describing the field of view used to measure the color matching functions.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Observer {
+    /// CIE 1931 2° standard observer.
+    #[default]
+    Degree2,
+    /// CIE 1964 10° standard observer.
+    Degree10,
+}
+
+//Convert from U32. Needed for converting from the settings AdwComboRow, which use indexes for values.
+impl From<u32> for Observer {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Degree2,
+            1 => Self::Degree10,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// The reference white used for CIE conversions, consisting of an illuminant
+/// as seen by a standard observer.
+///
+/// Colors are stored as sRGB, which is defined for D65 and the 2° observer. Converting
+/// to any other reference white uses the Bradford chromatic adaptation transform.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ReferenceWhite {
+    pub illuminant: Illuminant,
+    pub observer: Observer,
+}
+
+/// The Bradford cone response matrix.
+///
+/// Taken from <http://brucelindbloom.com/index.html?Eqn_ChromAdapt.html>.
+const BRADFORD: Matrix3 = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+/// The inverse of [`BRADFORD`].
+const BRADFORD_INVERSE: Matrix3 = [
+    [0.9869929, -0.1470543, 0.1599627],
+    [0.4323053, 0.5183603, 0.0492912],
+    [-0.0085287, 0.0400428, 0.9684867],
+];
+
+impl ReferenceWhite {
+    /// Create a new reference white from the given illuminant and observer.
+    pub const fn new(illuminant: Illuminant, observer: Observer) -> Self {
+        Self {
+            illuminant,
+            observer,
+        }
+    }
+
+    /// The white point of sRGB, D65 for the 2° observer.
+    pub const fn srgb() -> Self {
+        Self::new(Illuminant::D65, Observer::Degree2)
+    }
+
+    /// The white point of the CSS `lab()` and `lch()` functions, D50 for the 2° observer.
+    pub const fn d50() -> Self {
+        Self::new(Illuminant::D50, Observer::Degree2)
+    }
+
+    /// Returns the XYZ tristimulus values of the reference white, normalized to `Y = 1.0`.
+    ///
+    /// The 2° values match the ones used by palette, the 10° values are taken from ASTM E308.
+    pub fn xyz(&self) -> Xyz<Any, f32> {
+        let (x, z) = match (self.illuminant, self.observer) {
+            (Illuminant::A, Observer::Degree2) => (1.09850, 0.35585),
+            (Illuminant::C, Observer::Degree2) => (0.98074, 1.18232),
+            (Illuminant::D50, Observer::Degree2) => (0.96422, 0.82521),
+            (Illuminant::D55, Observer::Degree2) => (0.95682, 0.92149),
+            (Illuminant::D65, Observer::Degree2) => (0.95047, 1.08883),
+            (Illuminant::D75, Observer::Degree2) => (0.94972, 1.22638),
+            (Illuminant::E, _) => (1.0, 1.0),
+            (Illuminant::F2, Observer::Degree2) => (0.99186, 0.67393),
+            (Illuminant::F7, Observer::Degree2) => (0.95041, 1.08747),
+            (Illuminant::F11, Observer::Degree2) => (1.00962, 0.64350),
+            (Illuminant::A, Observer::Degree10) => (1.11144, 0.35200),
+            (Illuminant::C, Observer::Degree10) => (0.97285, 1.16145),
+            (Illuminant::D50, Observer::Degree10) => (0.96720, 0.81427),
+            (Illuminant::D55, Observer::Degree10) => (0.95799, 0.90926),
+            (Illuminant::D65, Observer::Degree10) => (0.94811, 1.07304),
+            (Illuminant::D75, Observer::Degree10) => (0.94416, 1.20641),
+            (Illuminant::F2, Observer::Degree10) => (1.03280, 0.69026),
+            (Illuminant::F7, Observer::Degree10) => (0.95792, 1.07687),
+            (Illuminant::F11, Observer::Degree10) => (1.03866, 0.65627),
+        };
+        Xyz::new(x, 1.0, z)
+    }
+
+    /// Adapts a color from the `source` reference white to this reference white.
+    ///
+    /// Uses the Bradford transform, as described in <http://brucelindbloom.com/index.html?Eqn_ChromAdapt.html>.
+    pub fn adapt_from(&self, color: Xyz<Any, f32>, source: ReferenceWhite) -> Xyz<Any, f32> {
+        if *self == source {
+            return color;
+        }
+
+        let source_cone = multiply(BRADFORD, source.xyz());
+        let target_cone = multiply(BRADFORD, self.xyz());
+
+        let cone = multiply(BRADFORD, color);
+        let adapted = Xyz::new(
+            cone.x * target_cone.x / source_cone.x,
+            cone.y * target_cone.y / source_cone.y,
+            cone.z * target_cone.z / source_cone.z,
+        );
+
+        multiply(BRADFORD_INVERSE, adapted)
+    }
+}
+
+/// Multiplies the given 3x3 matrix with the XYZ values.
+fn multiply(matrix: Matrix3, xyz: Xyz<Any, f32>) -> Xyz<Any, f32> {
+    let [x, y, z] = matrix::multiply(matrix, [xyz.x, xyz.y, xyz.z]);
+    Xyz::new(x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adapts_white_to_white() {
+        let d50 = ReferenceWhite::new(Illuminant::D50, Observer::Degree2);
+        let adapted = d50.adapt_from(ReferenceWhite::srgb().xyz(), ReferenceWhite::srgb());
+
+        assert!((adapted.x - d50.xyz().x).abs() < 1e-4);
+        assert!((adapted.y - d50.xyz().y).abs() < 1e-4);
+        assert!((adapted.z - d50.xyz().z).abs() < 1e-4);
+    }
+
+    #[test]
+    fn adapts_d65_to_d50() {
+        let d50 = ReferenceWhite::new(Illuminant::D50, Observer::Degree2);
+        let adapted = d50.adapt_from(Xyz::new(0.5, 0.4, 0.3), ReferenceWhite::srgb());
+
+        assert!((adapted.x - 0.518022).abs() < 1e-4);
+        assert!((adapted.y - 0.405850).abs() < 1e-4);
+        assert!((adapted.z - 0.227040).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,208 @@
+use palette::{
+    convert::{FromColorUnclamped, IntoColorUnclamped},
+    white_point::D65,
+    Clamp, WithAlpha, Xyz,
+};
+
+use super::{
+    matrix::{self, Matrix3},
+    pq,
+};
+
+/// Jzazbz with an alpha component.
+///
+/// Jzazbz is a perceptually uniform color space for HDR content. It is based on absolute
+/// luminance, so the luminance of the SDR white has to be known to convert relative colors.
+///
+/// Based on <https://doi.org/10.1364/OE.25.015131>
+#[derive(Debug, FromColorUnclamped, WithAlpha)]
+#[palette(skip_derives(Xyz), white_point = "D65")]
+pub struct Jzazbz {
+    /// The lightness of the color, where 0.0 is black.
+    pub jz: f32,
+    /// The position between red and green, where negative values are green and positive values are red.
+    pub az: f32,
+    /// The position between yellow and blue, where negative values are blue and positive values are yellow.
+    pub bz: f32,
+}
+
+const B: f32 = 1.15;
+const G: f32 = 0.66;
+const D: f32 = -0.56;
+const D0: f32 = 1.629_55e-11;
+/// The exponent of the PQ transfer function, which differs from the one of ST 2084.
+const P: f64 = 1.7 * 2523.0 / 32.0;
+
+const XYZ_TO_LMS: Matrix3 = [
+    [0.414_789_7, 0.579_999, 0.014_648],
+    [-0.201_51, 1.120_649, 0.053_100_8],
+    [-0.016_600_8, 0.2648, 0.668_479_9],
+];
+const LMS_TO_IAB: Matrix3 = [
+    [0.5, 0.5, 0.0],
+    [3.524, -4.066_708, 0.542_708],
+    [0.199_076, 1.096_799, -1.295_875],
+];
+
+impl Jzazbz {
+    /// Create a Jzazbz color
+    pub const fn new(jz: f32, az: f32, bz: f32) -> Self {
+        Self { jz, az, bz }
+    }
+
+    /// Convert from XYZ, where a luminance of 1.0 has the given absolute luminance in cd/m².
+    ///
+    /// Unlike the [`FromColorUnclamped`] implementation, this allows choosing the luminance at runtime.
+    pub fn from_xyz(color: Xyz<D65, f32>, white_luminance: f32) -> Self {
+        let [x, y, z] = [color.x, color.y, color.z].map(|value| value * white_luminance);
+        let x_prime = B * x - (B - 1.0) * z;
+        let y_prime = G * y - (G - 1.0) * x;
+
+        let lms = matrix::multiply(XYZ_TO_LMS, [x_prime, y_prime, z])
+            .map(|value| pq::encode(value / pq::PEAK_LUMINANCE, P));
+        let [iz, az, bz] = matrix::multiply(LMS_TO_IAB, lms);
+
+        Self::new((1.0 + D) * iz / (1.0 + D * iz) - D0, az, bz)
+    }
+
+    /// Convert into XYZ, where a luminance of 1.0 has the given absolute luminance in cd/m².
+    ///
+    /// Unlike the [`FromColorUnclamped`] implementation, this allows choosing the luminance at runtime.
+    pub fn into_xyz(self, white_luminance: f32) -> Xyz<D65, f32> {
+        let jz = self.jz + D0;
+        let iz = jz / (1.0 + D - D * jz);
+
+        let lms = matrix::multiply(matrix::invert(LMS_TO_IAB), [iz, self.az, self.bz])
+            .map(|value| pq::decode(value, P) * pq::PEAK_LUMINANCE);
+        let [x_prime, y_prime, z] = matrix::multiply(matrix::invert(XYZ_TO_LMS), lms);
+
+        let x = (x_prime + (B - 1.0) * z) / B;
+        let y = (y_prime + (G - 1.0) * x) / G;
+
+        Xyz::new(x, y, z) / white_luminance
+    }
+}
+
+impl FromColorUnclamped<Jzazbz> for Jzazbz {
+    fn from_color_unclamped(color: Jzazbz) -> Jzazbz {
+        color
+    }
+}
+
+impl FromColorUnclamped<Xyz<D65, f32>> for Jzazbz {
+    fn from_color_unclamped(color: Xyz<D65, f32>) -> Self {
+        Self::from_xyz(color, pq::SDR_WHITE_LUMINANCE)
+    }
+}
+
+impl FromColorUnclamped<Jzazbz> for Xyz<D65, f32> {
+    fn from_color_unclamped(color: Jzazbz) -> Self {
+        color.into_xyz(pq::SDR_WHITE_LUMINANCE)
+    }
+}
+
+impl Clamp for Jzazbz {
+    fn clamp(self) -> Self {
+        Jzazbz::new(self.jz.max(0.0), self.az, self.bz)
+    }
+}
+
+/// JzCzhz with an alpha component.
+///
+/// JzCzhz is the cylindrical form of [`Jzazbz`].
+#[derive(Debug, FromColorUnclamped, WithAlpha)]
+#[palette(skip_derives(Xyz), white_point = "D65")]
+pub struct Jzczhz {
+    /// The lightness of the color, where 0.0 is black.
+    pub jz: f32,
+    /// The colorfulness of the color, where 0.0 is gray.
+    pub chroma: f32,
+    /// The hue of the color, in degrees between 0.0 and 360.0.
+    pub hue: f32,
+}
+
+impl Jzczhz {
+    /// Create a JzCzhz color
+    pub const fn new(jz: f32, chroma: f32, hue: f32) -> Self {
+        Self { jz, chroma, hue }
+    }
+}
+
+impl FromColorUnclamped<Jzczhz> for Jzczhz {
+    fn from_color_unclamped(color: Jzczhz) -> Jzczhz {
+        color
+    }
+}
+
+impl FromColorUnclamped<Jzazbz> for Jzczhz {
+    fn from_color_unclamped(color: Jzazbz) -> Self {
+        let hue = color.bz.atan2(color.az).to_degrees();
+        Self::new(
+            color.jz,
+            color.az.hypot(color.bz),
+            if hue < 0.0 { hue + 360.0 } else { hue },
+        )
+    }
+}
+
+impl FromColorUnclamped<Jzczhz> for Jzazbz {
+    fn from_color_unclamped(color: Jzczhz) -> Self {
+        let (sin, cos) = color.hue.to_radians().sin_cos();
+        Self::new(color.jz, color.chroma * cos, color.chroma * sin)
+    }
+}
+
+impl FromColorUnclamped<Xyz<D65, f32>> for Jzczhz {
+    fn from_color_unclamped(color: Xyz<D65, f32>) -> Self {
+        Jzazbz::from_color_unclamped(color).into_color_unclamped()
+    }
+}
+
+impl FromColorUnclamped<Jzczhz> for Xyz<D65, f32> {
+    fn from_color_unclamped(color: Jzczhz) -> Self {
+        Jzazbz::from_color_unclamped(color).into_color_unclamped()
+    }
+}
+
+impl Clamp for Jzczhz {
+    fn clamp(self) -> Self {
+        Jzczhz::new(self.jz.max(0.0), self.chroma.max(0.0), self.hue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use palette::Srgb;
+
+    use super::*;
+
+    #[test]
+    fn converts_white() {
+        // the Jz of the SDR white only depends on its luminance
+        let jzazbz: Jzazbz = Srgb::new(1.0, 1.0, 1.0).into_color_unclamped();
+        assert!((jzazbz.jz - 0.2220).abs() < 1e-3, "{jzazbz:?}");
+        assert!(jzazbz.az.abs() < 1e-3);
+        assert!(jzazbz.bz.abs() < 1e-3);
+    }
+
+    #[test]
+    fn round_trips() {
+        let color = Srgb::new(0.18, 0.62, 0.95);
+        for white_luminance in [80.0, 203.0, 1000.0] {
+            let xyz: Xyz<D65, f32> = color.into_color_unclamped();
+            let srgb: Srgb = Jzazbz::from_xyz(xyz, white_luminance)
+                .into_xyz(white_luminance)
+                .into_color_unclamped();
+            assert!((srgb.red - color.red).abs() < 1e-4);
+            assert!((srgb.green - color.green).abs() < 1e-4);
+            assert!((srgb.blue - color.blue).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn converts_to_jzczhz() {
+        let jzczhz: Jzczhz = Jzazbz::new(0.1, 0.0, -0.02).into_color_unclamped();
+        assert!((jzczhz.chroma - 0.02).abs() < 1e-6);
+        assert!((jzczhz.hue - 270.0).abs() < 1e-3);
+    }
+}
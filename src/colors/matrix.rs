@@ -0,0 +1,48 @@
+/// A 3x3 matrix, stored as rows.
+pub type Matrix3 = [[f32; 3]; 3];
+
+/// Multiplies the matrix with the given column vector.
+pub fn multiply(matrix: Matrix3, vector: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+/// Returns the inverse of the given matrix.
+///
+/// The matrix is expected to be invertible, which is the case for all color space conversion matrices.
+pub fn invert(m: Matrix3) -> Matrix3 {
+    let cofactor = |row: usize, col: usize| {
+        let (r0, r1) = ((row + 1) % 3, (row + 2) % 3);
+        let (c0, c1) = ((col + 1) % 3, (col + 2) % 3);
+        m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+    };
+
+    let determinant =
+        m[0][0] * cofactor(0, 0) + m[0][1] * cofactor(0, 1) + m[0][2] * cofactor(0, 2);
+
+    // the inverse is the transposed cofactor matrix, divided by the determinant
+    [0, 1, 2].map(|row| [0, 1, 2].map(|col| cofactor(col, row) / determinant))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverts() {
+        let matrix = [[2.0, 0.0, 1.0], [1.0, 1.0, 0.0], [0.0, 3.0, 1.0]];
+        let inverse = invert(matrix);
+
+        for (index, column) in [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+            .into_iter()
+            .enumerate()
+        {
+            let result = multiply(matrix, multiply(inverse, column));
+            for (value, expected) in result.iter().zip(column) {
+                assert!(
+                    (value - expected).abs() < 1e-6,
+                    "column {index}: {result:?}"
+                );
+            }
+        }
+    }
+}
@@ -1,9 +1,24 @@
+pub mod cct;
 pub mod cmyk;
 pub mod color;
 pub mod color_names;
+pub mod css;
+pub mod diagnostic;
+pub mod hex;
+pub mod hpluv;
 pub mod hunterlab;
+pub mod ictcp;
+pub mod illuminant;
+pub mod jzazbz;
+mod matrix;
 mod notation;
+pub mod packed;
 pub mod parser;
 pub mod position;
+mod pq;
+pub mod rgb_space;
+pub mod user_palette;
+pub mod viewing_conditions;
+pub mod ycbcr;
 
-pub use notation::Notation;
+pub use notation::{DetectedColor, Notation, NotationOptions};
@@ -1,19 +1,41 @@
 use std::str::FromStr;
 
 use gtk::{gio, prelude::SettingsExt};
-use palette::IntoColor;
+use palette::{
+    cam16::{Cam16Jmh, Cam16UcsJab},
+    convert::IntoColorUnclamped,
+    white_point::Any,
+    IntoColor,
+};
 
 use crate::{
-    colors::{cmyk::Cmyka, hunterlab::HunterLab},
+    colors::{
+        cct::Cct,
+        cmyk::Cmyka,
+        hpluv::Hpluv,
+        hunterlab::HunterLab,
+        ictcp::Ictcp,
+        jzazbz::{Jzazbz, Jzczhz},
+    },
     config,
     widgets::preferences::color_format::ColorFormatObject,
 };
 
 use super::{
-    color::{Color, ColorError},
-    color_names::{self, ColorNameSources},
-    parser,
+    color::{round_half_up, Color, ColorError},
+    color_names::{self, ColorNameSources, NameMatch},
+    css::{CssStyle, CssSyntax, PredefinedSpace},
+    diagnostic::{Diagnostic, DiagnosticKind, Expectation},
+    hex::{HexPrefix, HexStyle},
+    illuminant::{Illuminant, Observer, ReferenceWhite},
+    packed,
+    parser::{self, Clamps, SyntaxError},
     position::AlphaPosition,
+    pq,
+    rgb_space::RgbSpace,
+    user_palette,
+    viewing_conditions::{Surround, ViewingConditions},
+    ycbcr::{YcbcrEncoding, YcbcrMatrix, YcbcrRange},
 };
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, glib::Enum)]
@@ -26,6 +48,7 @@ pub enum Notation {
     Hsv,
     Cmyk,
     Xyz,
+    Xyy,
     Lab,
     Hwb,
     Hcl,
@@ -34,87 +57,538 @@ pub enum Notation {
     HunterLab,
     Oklab,
     Oklch,
+    Okhsl,
+    Okhsv,
+    DisplayP3,
+    Rec2020,
+    AdobeRgb,
+    ProPhotoRgb,
+    CssColor,
+    CssLab,
+    CssLch,
+    Hsluv,
+    Hpluv,
+    Luv,
+    Lchuv,
+    Jzazbz,
+    Jzczhz,
+    Ictcp,
+    Cam16,
+    Cam16Ucs,
+    Ycbcr,
+    Cct,
+    LinearFloats,
+    Floats,
+    UiColor,
+    SwiftUi,
+    Compose,
+    Flutter,
+    AndroidXml,
+    QColor,
+    CSharp,
+    JavaAwt,
+    Unity,
+    Glsl,
+    DecimalInt,
+    HexInt,
+    Colorref,
+    Rgb565,
+    Rgb555,
+}
+
+/// The preferences, that determine how colors are formatted and parsed.
+#[derive(Debug, Clone)]
+pub struct NotationOptions {
+    pub alpha_position: AlphaPosition,
+    pub hex_style: HexStyle,
+    pub css_style: CssStyle,
+    /// The number of digits shown after the decimal point.
+    pub precision: usize,
+    pub name_sources: ColorNameSources,
+    /// The identifiers of the enabled palettes from the user data directory, in the order of their priority.
+    pub user_name_sources: Vec<String>,
+    /// The largest perceptual difference at which the closest name is still shown for a color.
+    pub name_tolerance: f32,
+    pub reference_white: ReferenceWhite,
+    /// The absolute luminance of the SDR white in cd/m², used by the HDR color spaces.
+    pub sdr_white_luminance: f32,
+    pub viewing_conditions: ViewingConditions,
+    pub ycbcr_encoding: YcbcrEncoding,
+}
+
+impl NotationOptions {
+    /// Reads the options from the settings.
+    pub fn from_settings(settings: &gio::Settings) -> Self {
+        Self {
+            alpha_position: AlphaPosition::from(settings.int("alpha-position") as u32),
+            hex_style: HexStyle {
+                short_form: settings.boolean("hex-short-form"),
+                uppercase: settings.boolean("hex-uppercase"),
+                prefix: HexPrefix::from(settings.int("hex-prefix") as u32),
+            },
+            css_style: CssStyle {
+                rgb: CssSyntax::from(settings.int("css-rgb-syntax") as u32),
+                hsl: CssSyntax::from(settings.int("css-hsl-syntax") as u32),
+                hwb: CssSyntax::from(settings.int("css-hwb-syntax") as u32),
+                color_space: PredefinedSpace::from(settings.int("css-color-space") as u32),
+            },
+            precision: settings.uint("precision-digits") as usize,
+            name_sources: ColorNameSources::from_bits(settings.uint("name-sources-flag"))
+                .unwrap_or(ColorNameSources::empty()),
+            user_name_sources: settings.get::<Vec<String>>("user-name-sources"),
+            name_tolerance: settings.double("name-tolerance") as f32,
+            reference_white: ReferenceWhite::new(
+                Illuminant::from(settings.int("cie-illuminants") as u32),
+                Observer::from(settings.int("cie-standard-observer") as u32),
+            ),
+            sdr_white_luminance: settings.uint("sdr-white-luminance") as f32,
+            viewing_conditions: ViewingConditions {
+                adapting_luminance: settings.double("cam16-adapting-luminance") as f32,
+                background_luminance: settings.uint("cam16-background-luminance") as f32 / 100.0,
+                surround: Surround::from(settings.int("cam16-surround") as u32),
+            },
+            ycbcr_encoding: YcbcrEncoding {
+                matrix: YcbcrMatrix::from(settings.int("ycbcr-matrix") as u32),
+                range: YcbcrRange::from(settings.int("ycbcr-range") as u32),
+                bit_depth: if settings.int("ycbcr-bit-depth") == 1 {
+                    10
+                } else {
+                    8
+                },
+            },
+        }
+    }
+}
+
+impl Default for NotationOptions {
+    fn default() -> Self {
+        Self {
+            alpha_position: AlphaPosition::None,
+            hex_style: HexStyle::default(),
+            css_style: CssStyle::default(),
+            precision: 2,
+            name_sources: ColorNameSources::empty(),
+            user_name_sources: Vec::new(),
+            name_tolerance: 10.0,
+            reference_white: ReferenceWhite::default(),
+            sdr_white_luminance: pq::SDR_WHITE_LUMINANCE,
+            viewing_conditions: ViewingConditions::default(),
+            ycbcr_encoding: YcbcrEncoding::default(),
+        }
+    }
+}
+
+/// A color parsed from an input of an unknown notation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedColor {
+    /// The first notation in the list of formats, that parses the whole input.
+    pub notation: Notation,
+    pub color: Color,
+    /// Other notations, that also parse the whole input, but result in a different color.
+    pub alternatives: Vec<(Notation, Color)>,
+}
+
+impl DetectedColor {
+    /// Whether the input can be read as more than one color.
+    pub fn is_ambiguous(&self) -> bool {
+        !self.alternatives.is_empty()
+    }
 }
 
 impl Notation {
-    pub fn parse(&self, input: &str, name_sources: ColorNameSources) -> Result<Color, ColorError> {
+    /// All notations, in the default order of the enum.
+    pub const ALL: [Notation; 52] = [
+        Notation::Hex,
+        Notation::Rgb,
+        Notation::Hsl,
+        Notation::Hsv,
+        Notation::Cmyk,
+        Notation::Xyz,
+        Notation::Xyy,
+        Notation::Lab,
+        Notation::Hwb,
+        Notation::Hcl,
+        Notation::Name,
+        Notation::Lms,
+        Notation::HunterLab,
+        Notation::Oklab,
+        Notation::Oklch,
+        Notation::Okhsl,
+        Notation::Okhsv,
+        Notation::DisplayP3,
+        Notation::Rec2020,
+        Notation::AdobeRgb,
+        Notation::ProPhotoRgb,
+        Notation::CssColor,
+        Notation::CssLab,
+        Notation::CssLch,
+        Notation::Hsluv,
+        Notation::Hpluv,
+        Notation::Luv,
+        Notation::Lchuv,
+        Notation::Jzazbz,
+        Notation::Jzczhz,
+        Notation::Ictcp,
+        Notation::Cam16,
+        Notation::Cam16Ucs,
+        Notation::Ycbcr,
+        Notation::Cct,
+        Notation::LinearFloats,
+        Notation::Floats,
+        Notation::UiColor,
+        Notation::SwiftUi,
+        Notation::Compose,
+        Notation::Flutter,
+        Notation::AndroidXml,
+        Notation::QColor,
+        Notation::CSharp,
+        Notation::JavaAwt,
+        Notation::Unity,
+        Notation::Glsl,
+        Notation::DecimalInt,
+        Notation::HexInt,
+        Notation::Colorref,
+        Notation::Rgb565,
+        Notation::Rgb555,
+    ];
+
+    /// Parses the input, describing what is wrong with it if it cannot be parsed.
+    pub fn parse(&self, input: &str) -> Result<Color, Diagnostic> {
+        self.parse_diagnosed(input).map(|(color, _)| color)
+    }
+
+    /// Parses the input, returning the color together with the diagnostics
+    /// of the values which have been clamped or ignored.
+    pub fn parse_diagnosed(&self, input: &str) -> Result<(Color, Vec<Diagnostic>), Diagnostic> {
         let settings = gio::Settings::new(config::APP_ID);
-        let (_, color) = match self {
-            Notation::Hex => parser::hex_color(
-                input,
-                AlphaPosition::from(settings.int("alpha-position") as u32),
-            ),
-            Notation::Rgb => parser::rgb(input),
-            Notation::Hsl => parser::hsl(input),
-            Notation::Hsv => parser::hsv(input),
-            Notation::Cmyk => parser::cmyk(input),
-            Notation::Xyz => parser::xyz(input),
-            Notation::Lab => parser::cielab(input),
-            Notation::Hwb => parser::hwb(input),
-            Notation::Hcl => parser::lch(input),
-            Notation::Lms => parser::lms(input),
-            Notation::HunterLab => parser::hunter_lab(input),
-            Notation::Oklab => parser::oklab(input),
-            Notation::Oklch => parser::oklch(input),
+        self.parse_diagnosed_with(input, &NotationOptions::from_settings(&settings))
+    }
+
+    /// Parses the input using the given options instead of the ones stored in the settings.
+    #[cfg(test)]
+    fn parse_with(&self, input: &str, options: &NotationOptions) -> Result<Color, Diagnostic> {
+        self.parse_diagnosed_with(input, options)
+            .map(|(color, _)| color)
+    }
+
+    /// Parses the input with diagnostics, using the given options instead of the ones stored in the settings.
+    fn parse_diagnosed_with(
+        &self,
+        input: &str,
+        options: &NotationOptions,
+    ) -> Result<(Color, Vec<Diagnostic>), Diagnostic> {
+        let clamps = Clamps::default();
+        let (remaining, color) = self.parse_partial(input, options, &clamps)?;
+
+        let mut diagnostics = clamps
+            .spans(input)
+            .into_iter()
+            .map(|span| Diagnostic::new(input, span, DiagnosticKind::Clamped))
+            .collect::<Vec<_>>();
+
+        let ignored = remaining.trim_start();
+        if !ignored.trim_end().is_empty() {
+            let offset = input.len() - ignored.len();
+            let span = offset..offset + ignored.trim_end().len();
+            diagnostics.push(Diagnostic::new(input, span, DiagnosticKind::Ignored));
+        }
+
+        Ok((color, diagnostics))
+    }
+
+    /// Parses the start of the input, returning the remaining input together with the color.
+    ///
+    /// The values, that have been clamped, are added to the given clamps.
+    fn parse_partial<'a>(
+        &self,
+        input: &'a str,
+        options: &NotationOptions,
+        clamps: &'a Clamps,
+    ) -> Result<(&'a str, Color), Diagnostic> {
+        let NotationOptions {
+            alpha_position,
+            name_sources,
+            ref user_name_sources,
+            reference_white,
+            sdr_white_luminance,
+            viewing_conditions,
+            ycbcr_encoding,
+            ..
+        } = *options;
+        let result = match self {
+            Notation::Hex => parser::hex_color(input, alpha_position),
+            Notation::Rgb => parser::rgb(input, clamps),
+            Notation::Hsl => parser::hsl(input, clamps),
+            Notation::Hsv => parser::hsv(input, clamps),
+            Notation::Cmyk => parser::cmyk(input, clamps),
+            Notation::Xyz => parser::xyz(input, reference_white),
+            Notation::Xyy => parser::xyy(input, reference_white),
+            Notation::Lab => parser::cielab(input, reference_white, clamps),
+            Notation::Hwb => parser::hwb(input, clamps),
+            Notation::Hcl => parser::cielch(input, reference_white, clamps),
+            Notation::Lms => parser::lms(input, reference_white),
+            Notation::HunterLab => parser::hunter_lab(input, reference_white),
+            Notation::Oklab => parser::oklab(input, clamps),
+            Notation::Oklch => parser::oklch(input, clamps),
+            Notation::Okhsl => parser::okhsl(input, clamps),
+            Notation::Okhsv => parser::okhsv(input, clamps),
+            Notation::DisplayP3 => parser::color_function(input, clamps)
+                .or_else(|_| parser::rgb_space(input, RgbSpace::DisplayP3, clamps)),
+            Notation::Rec2020 => parser::color_function(input, clamps)
+                .or_else(|_| parser::rgb_space(input, RgbSpace::Rec2020, clamps)),
+            Notation::AdobeRgb => parser::color_function(input, clamps)
+                .or_else(|_| parser::rgb_space(input, RgbSpace::AdobeRgb, clamps)),
+            Notation::ProPhotoRgb => parser::color_function(input, clamps)
+                .or_else(|_| parser::rgb_space(input, RgbSpace::ProPhotoRgb, clamps)),
+            Notation::CssColor => parser::color_function(input, clamps),
+            Notation::CssLab => parser::lab(input, clamps),
+            Notation::CssLch => parser::lch(input, clamps),
+            Notation::Hsluv => parser::hsluv(input, clamps),
+            Notation::Hpluv => parser::hpluv(input, clamps),
+            Notation::Luv => parser::cieluv(input, reference_white, clamps),
+            Notation::Lchuv => parser::lchuv(input, reference_white, clamps),
+            Notation::Jzazbz => parser::jzazbz(input, sdr_white_luminance, clamps),
+            Notation::Jzczhz => parser::jzczhz(input, sdr_white_luminance, clamps),
+            Notation::Ictcp => parser::ictcp(input, sdr_white_luminance, clamps),
+            Notation::Cam16 => parser::cam16(input, viewing_conditions, clamps),
+            Notation::Cam16Ucs => parser::cam16_ucs(input, viewing_conditions, clamps),
+            Notation::Ycbcr => parser::ycbcr(input, ycbcr_encoding, clamps),
+            Notation::Cct => parser::cct(input),
+            Notation::LinearFloats => parser::linear_floats(input, clamps),
+            Notation::Floats => parser::floats(input, clamps),
+            Notation::UiColor => parser::uicolor(input),
+            Notation::SwiftUi => parser::swiftui(input),
+            Notation::Compose => parser::compose(input),
+            Notation::Flutter => parser::flutter(input),
+            Notation::AndroidXml => parser::android_xml(input),
+            Notation::QColor => parser::qcolor(input),
+            Notation::CSharp => parser::csharp(input),
+            Notation::JavaAwt => parser::java_awt(input),
+            Notation::Unity => parser::unity(input, clamps),
+            Notation::Glsl => parser::glsl(input, clamps),
+            Notation::DecimalInt | Notation::HexInt => parser::rgb_integer(input, alpha_position),
+            Notation::Colorref => parser::colorref(input),
+            Notation::Rgb565 => parser::rgb565(input),
+            Notation::Rgb555 => parser::rgb555(input, alpha_position),
             Notation::Name => {
-                return color_names::color(input, name_sources)
-                    .ok_or(ColorError::ParsingError("No name found".to_owned()));
+                return color_names::color(input.trim(), name_sources, user_name_sources)
+                    .map(|color| ("", color))
+                    .ok_or_else(|| {
+                        let start = input.len() - input.trim_start().len();
+                        Diagnostic::new(
+                            input,
+                            start..start + input.trim().len(),
+                            DiagnosticKind::Expected(Expectation::ColorName),
+                        )
+                    });
             }
-        }?;
-        Ok(color)
+        };
+        result.map_err(|error| self.diagnostic(input, error, options))
     }
 
-    pub fn as_str(
+    /// Describes why the input could not be parsed, pointing at the token where the parser stopped.
+    fn diagnostic(
         &self,
-        color: Color,
-        alpha_position: AlphaPosition,
-        precision: usize,
-        name_sources: ColorNameSources,
-    ) -> String {
-        let percent = |value: f32| (value * 100.0).round();
+        input: &str,
+        error: nom::Err<SyntaxError<&str>>,
+        options: &NotationOptions,
+    ) -> Diagnostic {
+        let (offset, expected) = match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                (input.len() - error.input.len(), error.expected)
+            }
+            nom::Err::Incomplete(_) => (input.len(), None),
+        };
+
+        let expected = match expected {
+            Some(expected) => expected,
+            // without a more specific expectation, show how a value in this notation looks like
+            None => Expectation::Example(self.as_str(Color::rgb(46, 52, 64), options)),
+        };
+        Diagnostic::at_token(input, offset, DiagnosticKind::Expected(expected))
+    }
+
+    /// Parses the input in any notation, without knowing it beforehand.
+    ///
+    /// Returns `None` if no notation can parse the whole input.
+    /// Names are looked up in all palettes of the user data directory, including the disabled ones.
+    pub fn detect(input: &str) -> Option<DetectedColor> {
+        Self::detect_with(input, &Self::ALL, &Self::detection_options())
+    }
+
+    /// Parses the input in one of the notations that cannot be mistaken for plain text,
+    /// such as a search query, which may be a number or a word instead of a color.
+    ///
+    /// Returns `None` if none of these notations can parse the whole input.
+    pub fn detect_unmistakable(input: &str) -> Option<DetectedColor> {
+        Self::detect_with(
+            input,
+            &Self::unmistakable(input),
+            &Self::detection_options(),
+        )
+    }
+
+    /// The options to detect notations with, which are the ones stored in the settings,
+    /// including all palettes of the user data directory.
+    fn detection_options() -> NotationOptions {
+        let settings = gio::Settings::new(config::APP_ID);
+        NotationOptions {
+            user_name_sources: user_palette::all()
+                .iter()
+                .map(|palette| palette.id.clone())
+                .collect(),
+            ..NotationOptions::from_settings(&settings)
+        }
+    }
+
+    /// Returns the notations that cannot be mistaken for plain text, which are functions,
+    /// code literals and names.
+    ///
+    /// Hex codes are only included if the input starts with a `#`, as plain numbers could be hex codes as well.
+    fn unmistakable(input: &str) -> Vec<Notation> {
+        let is_hex_code = input.trim_start().starts_with('#');
+        Self::ALL
+            .into_iter()
+            .filter(|notation| match notation {
+                Notation::Hex => is_hex_code,
+                Notation::Rgb
+                | Notation::Hsl
+                | Notation::Hsv
+                | Notation::Cmyk
+                | Notation::Hwb
+                | Notation::Name
+                | Notation::Oklab
+                | Notation::Oklch
+                | Notation::CssColor
+                | Notation::CssLab
+                | Notation::CssLch
+                | Notation::UiColor
+                | Notation::SwiftUi
+                | Notation::Compose
+                | Notation::Flutter
+                | Notation::AndroidXml
+                | Notation::QColor
+                | Notation::CSharp
+                | Notation::JavaAwt
+                | Notation::Unity
+                | Notation::Glsl => true,
+                _ => false,
+            })
+            .collect()
+    }
+
+    /// Detects the notation of the input among the given notations, using the given options
+    /// instead of the ones stored in the settings.
+    ///
+    /// Names are looked up in all bundled palettes, regardless of the enabled name sources,
+    /// and in the palettes of the user data directory listed in the options.
+    fn detect_with(
+        input: &str,
+        notations: &[Notation],
+        options: &NotationOptions,
+    ) -> Option<DetectedColor> {
+        let input = input.trim();
+        let options = NotationOptions {
+            name_sources: ColorNameSources::all(),
+            ..options.clone()
+        };
+
+        // only accept notations that parse the whole input, so that `#2e3440` is not
+        // mistaken for a hex code with only some of its digits
+        let mut matches = notations.iter().filter_map(|&notation| {
+            match notation.parse_partial(input, &options, &Clamps::default()) {
+                Ok((remaining, color)) if remaining.trim().is_empty() => Some((notation, color)),
+                _ => None,
+            }
+        });
+
+        let (notation, color) = matches.next()?;
+        let mut alternatives: Vec<(Notation, Color)> = Vec::new();
+        for (alternative, alternative_color) in matches {
+            // notations sharing the same syntax, such as the color() function, are not ambiguous
+            let is_new = alternative_color.rgba8() != color.rgba8()
+                && alternatives
+                    .iter()
+                    .all(|(_, other)| other.rgba8() != alternative_color.rgba8());
+            if is_new {
+                alternatives.push((alternative, alternative_color));
+            }
+        }
+
+        Some(DetectedColor {
+            notation,
+            color,
+            alternatives,
+        })
+    }
+
+    pub fn as_str(&self, color: Color, options: &NotationOptions) -> String {
+        let NotationOptions {
+            alpha_position,
+            hex_style,
+            css_style,
+            precision,
+            reference_white,
+            sdr_white_luminance,
+            viewing_conditions,
+            ycbcr_encoding,
+            ..
+        } = *options;
+        let percent = |value: f32| round_half_up(value * 100.0);
+        // round to the displayed precision, which also prevents showing `-0.00`
+        let fixed = |value: f32| {
+            let scale = 10f32.powi(precision as i32);
+            round_half_up(value * scale) / scale
+        };
+        // the hue is meaningless without chroma, so show it as zero instead of rounding noise
+        let polar_hue = |chroma: f32, hue: f32| {
+            if fixed(chroma) == 0.0 {
+                0.0
+            } else {
+                fixed(hue) % 360.0
+            }
+        };
+        // a hue of 360° is the same as 0°
+        let degrees = |hue: f32| round_half_up(hue) % 360.0;
         let pretty_percent = |value: f32| match value {
             1.0 => "1".to_string(),
             0.0 => "0".to_string(),
             _ => format!("{:.2}", value),
         };
+        // small values, such as the ones of the HDR color spaces, are shown with two more digits
+        let fine_precision = precision + 2;
+        let fixed_fine = |value: f32| {
+            let scale = 10f32.powi(fine_precision as i32);
+            round_half_up(value * scale) / scale
+        };
+        let css_alpha = match alpha_position {
+            AlphaPosition::End => format!(" / {}", pretty_percent(percent(color.alpha) / 100.0)),
+            _ => String::new(),
+        };
 
         match self {
-            Notation::Hex => {
-                let hex = |value: f32| format!("{:02X}", (value * 255.0) as u8);
-                let (r, g, b, a) = (
-                    hex(color.red),
-                    hex(color.green),
-                    hex(color.blue),
-                    hex(color.alpha),
-                );
-                match alpha_position {
-                    AlphaPosition::Start => format!("#{}{}{}{}", a, r, g, b),
-                    AlphaPosition::End => format!("#{}{}{}{}", r, g, b, a),
-                    AlphaPosition::None => format!("#{}{}{}", r, g, b),
-                }
-            }
+            Notation::Hex => hex_style.format(color, alpha_position),
             Notation::Rgb => {
-                let rgb = |a: f32| (a * 255.0).round() as u8;
-                let (r, g, b, a) = (
-                    rgb(color.red),
-                    rgb(color.green),
-                    rgb(color.blue),
-                    pretty_percent(color.alpha),
-                );
-                match alpha_position {
-                    AlphaPosition::End => format!("rgba({}, {}, {}, {})", r, g, b, a),
+                let [r, g, b, _] = color.rgba8();
+                match (css_style.rgb, alpha_position) {
+                    (CssSyntax::Modern, _) => format!("rgb({} {} {}{})", r, g, b, css_alpha),
+                    // always include the decimal point, as integers are parsed as 8-bit values
+                    (_, AlphaPosition::End) => {
+                        format!("rgba({}, {}, {}, {:.2})", r, g, b, color.alpha)
+                    }
                     _ => format!("rgb({}, {}, {})", r, g, b),
                 }
             }
             Notation::Hsl => {
                 let hsl: palette::Hsl = color.color.into_color();
                 let (h, s, l) = (
-                    hsl.hue.into_positive_degrees(),
+                    degrees(hsl.hue.into_positive_degrees()),
                     percent(hsl.saturation),
                     percent(hsl.lightness),
                 );
-                match alpha_position {
-                    AlphaPosition::End => format!(
+                match (css_style.hsl, alpha_position) {
+                    (CssSyntax::Modern, _) => format!("hsl({} {}% {}%{})", h, s, l, css_alpha),
+                    (_, AlphaPosition::End) => format!(
                         "hsla({}, {}%, {}%, {})",
                         h,
                         s,
@@ -128,7 +602,7 @@ impl Notation {
                 let hsv: palette::Hsv = color.color.into_color();
                 format!(
                     "hsv({}, {}%, {}%)",
-                    hsv.hue.into_positive_degrees(),
+                    degrees(hsv.hue.into_positive_degrees()),
                     percent(hsv.saturation),
                     percent(hsv.value)
                 )
@@ -144,94 +618,511 @@ impl Notation {
                 )
             }
             Notation::Xyz => {
-                let xyz: palette::Xyz = color.color.into_color();
+                let xyz = color.to_xyz(reference_white);
                 format!(
                     "XYZ({:.precision$}, {:.precision$}, {:.precision$})",
-                    xyz.x * 100.0,
-                    xyz.y * 100.0,
-                    xyz.z * 100.0,
+                    fixed(xyz.x * 100.0),
+                    fixed(xyz.y * 100.0),
+                    fixed(xyz.z * 100.0),
+                )
+            }
+            Notation::Xyy => {
+                let xyy = color.to_xyy(reference_white);
+                format!(
+                    "xyY({:.precision$}, {:.precision$}, {:.precision$})",
+                    fixed(xyy.x),
+                    fixed(xyy.y),
+                    fixed(xyy.luma * 100.0),
                 )
             }
             Notation::Lab => {
-                let lab: palette::Lab = color.color.into_color();
+                let lab = color.to_lab(reference_white);
                 format!(
-                    "lab({:.precision$}, {:.precision$}, {:.precision$})",
-                    lab.l, lab.a, lab.b,
+                    "cielab({:.precision$}, {:.precision$}, {:.precision$})",
+                    fixed(lab.l),
+                    fixed(lab.a),
+                    fixed(lab.b),
                 )
             }
             Notation::Hwb => {
                 let hwb: palette::Hwb = color.color.into_color();
-                format!(
-                    "hwb({}, {}%, {}%)",
-                    hwb.hue.into_positive_degrees(),
+                let (h, w, b) = (
+                    degrees(hwb.hue.into_positive_degrees()),
                     percent(hwb.whiteness),
-                    percent(hwb.blackness)
-                )
+                    percent(hwb.blackness),
+                );
+                match css_style.hwb {
+                    CssSyntax::Modern => format!("hwb({} {}% {}%{})", h, w, b, css_alpha),
+                    CssSyntax::Legacy => format!("hwb({}, {}%, {}%)", h, w, b),
+                }
             }
             Notation::Hcl => {
-                let lch: palette::Lch = color.color.into_color();
+                let lch: palette::Lch<Any> =
+                    color.to_lab(reference_white).color.into_color_unclamped();
                 format!(
-                    "lch({:.precision$}, {:.precision$}, {:.precision$})",
-                    lch.l,
-                    lch.chroma,
-                    lch.hue.into_positive_degrees(),
+                    "cielch({:.precision$}, {:.precision$}, {:.precision$})",
+                    fixed(lch.l),
+                    fixed(lch.chroma),
+                    polar_hue(lch.chroma, lch.hue.into_positive_degrees()),
                 )
             }
             Notation::Lms => {
-                let (l, m, s) = color.to_lms();
+                let (l, m, s) = color.to_lms(reference_white);
                 format!(
                     "L: {:.precision$}, M: {:.precision$}, S: {:.precision$}",
-                    l, m, s,
+                    fixed(l),
+                    fixed(m),
+                    fixed(s),
                 )
             }
             Notation::HunterLab => {
-                let lab: HunterLab = color.color.into_color();
+                let lab =
+                    HunterLab::from_xyz(color.to_xyz(reference_white).color, reference_white.xyz());
                 format!(
                     "L: {:.precision$}, a: {:.precision$}, b: {:.precision$}",
-                    lab.l, lab.a, lab.b,
+                    fixed(lab.l),
+                    fixed(lab.a),
+                    fixed(lab.b),
                 )
             }
             Notation::Oklab => {
-                let oklab: palette::Oklab = color.color.into_color();
+                let oklab: palette::Oklab = color.color.into_color_unclamped();
                 match alpha_position {
                     AlphaPosition::End => format!(
-                        "oklab({}% {:.precision$} {:.precision$} / {})",
-                        percent(oklab.l),
-                        oklab.a,
-                        oklab.b,
+                        "oklab({:.precision$}% {:.precision$} {:.precision$} / {})",
+                        fixed(oklab.l * 100.0),
+                        fixed(oklab.a),
+                        fixed(oklab.b),
                         pretty_percent(percent(color.alpha) / 100.0),
                     ),
                     _ => format!(
-                        "oklab({}% {:.precision$} {:.precision$})",
-                        percent(oklab.l),
-                        oklab.a,
-                        oklab.b,
+                        "oklab({:.precision$}% {:.precision$} {:.precision$})",
+                        fixed(oklab.l * 100.0),
+                        fixed(oklab.a),
+                        fixed(oklab.b),
                     ),
                 }
             }
             Notation::Oklch => {
-                let oklch: palette::Oklch = color.color.into_color();
+                let oklch: palette::Oklch = color.color.into_color_unclamped();
+                match alpha_position {
+                    AlphaPosition::End => format!(
+                        "oklch({:.precision$}% {:.precision$} {:.precision$} / {})",
+                        fixed(oklch.l * 100.0),
+                        fixed(oklch.chroma),
+                        polar_hue(oklch.chroma, oklch.hue.into_positive_degrees()),
+                        pretty_percent(percent(color.alpha) / 100.0),
+                    ),
+                    _ => format!(
+                        "oklch({:.precision$}% {:.precision$} {:.precision$})",
+                        fixed(oklch.l * 100.0),
+                        fixed(oklch.chroma),
+                        polar_hue(oklch.chroma, oklch.hue.into_positive_degrees()),
+                    ),
+                }
+            }
+            Notation::Okhsl | Notation::Okhsv => {
+                let (name, hue, saturation, l) = if *self == Notation::Okhsl {
+                    let okhsl: palette::Okhsl = color.color.into_color_unclamped();
+                    ("okhsl", okhsl.hue, okhsl.saturation, okhsl.lightness)
+                } else {
+                    let okhsv: palette::Okhsv = color.color.into_color_unclamped();
+                    ("okhsv", okhsv.hue, okhsv.saturation, okhsv.value)
+                };
+                let (saturation, l) = (saturation * 100.0, l * 100.0);
+                // black does not have a saturation, neither does white in OKHSL
+                let saturation =
+                    if fixed(l) == 0.0 || (*self == Notation::Okhsl && fixed(l) == 100.0) {
+                        0.0
+                    } else {
+                        saturation
+                    };
+                let hue = polar_hue(saturation, hue.into_positive_degrees());
+                match alpha_position {
+                    AlphaPosition::End => format!(
+                        "{}({:.precision$}, {:.precision$}%, {:.precision$}%, {})",
+                        name,
+                        hue,
+                        fixed(saturation),
+                        fixed(l),
+                        pretty_percent(percent(color.alpha) / 100.0),
+                    ),
+                    _ => format!(
+                        "{}({:.precision$}, {:.precision$}%, {:.precision$}%)",
+                        name,
+                        hue,
+                        fixed(saturation),
+                        fixed(l),
+                    ),
+                }
+            }
+            Notation::DisplayP3
+            | Notation::Rec2020
+            | Notation::AdobeRgb
+            | Notation::ProPhotoRgb => {
+                let space = match self {
+                    Notation::DisplayP3 => RgbSpace::DisplayP3,
+                    Notation::Rec2020 => RgbSpace::Rec2020,
+                    Notation::AdobeRgb => RgbSpace::AdobeRgb,
+                    _ => RgbSpace::ProPhotoRgb,
+                };
+                let [r, g, b] = space.components(color);
+                match alpha_position {
+                    AlphaPosition::End => format!(
+                        "color({} {:.precision$} {:.precision$} {:.precision$} / {})",
+                        space.css_name(),
+                        fixed(r),
+                        fixed(g),
+                        fixed(b),
+                        pretty_percent(percent(color.alpha) / 100.0),
+                    ),
+                    _ => format!(
+                        "color({} {:.precision$} {:.precision$} {:.precision$})",
+                        space.css_name(),
+                        fixed(r),
+                        fixed(g),
+                        fixed(b),
+                    ),
+                }
+            }
+            Notation::CssColor => {
+                let space = css_style.color_space;
+                let [a, b, c] = space.components(color);
                 match alpha_position {
                     AlphaPosition::End => format!(
-                        "oklch({}% {:.precision$} {:.precision$} / {})",
-                        percent(oklch.l),
-                        oklch.chroma,
-                        oklch.hue.into_positive_degrees(),
+                        "color({} {:.precision$} {:.precision$} {:.precision$} / {})",
+                        space.css_name(),
+                        fixed(a),
+                        fixed(b),
+                        fixed(c),
                         pretty_percent(percent(color.alpha) / 100.0),
                     ),
                     _ => format!(
-                        "oklch({}% {:.precision$} {:.precision$})",
-                        percent(oklch.l),
-                        oklch.chroma,
-                        oklch.hue.into_positive_degrees(),
+                        "color({} {:.precision$} {:.precision$} {:.precision$})",
+                        space.css_name(),
+                        fixed(a),
+                        fixed(b),
+                        fixed(c),
                     ),
                 }
             }
-            Notation::Name => color_names::name(color, name_sources)
-                .unwrap_or_else(|| gettextrs::gettext("Not named")),
+            // CSS defines lab() and lch() relative to D50, regardless of the reference white
+            Notation::CssLab => {
+                let lab = color.to_lab(ReferenceWhite::d50());
+                format!(
+                    "lab({:.precision$}% {:.precision$} {:.precision$}{css_alpha})",
+                    fixed(lab.l),
+                    fixed(lab.a),
+                    fixed(lab.b),
+                )
+            }
+            Notation::CssLch => {
+                let lch: palette::Lch<Any> = color
+                    .to_lab(ReferenceWhite::d50())
+                    .color
+                    .into_color_unclamped();
+                format!(
+                    "lch({:.precision$}% {:.precision$} {:.precision$}{css_alpha})",
+                    fixed(lch.l),
+                    fixed(lch.chroma),
+                    polar_hue(lch.chroma, lch.hue.into_positive_degrees()),
+                )
+            }
+            Notation::Hsluv | Notation::Hpluv => {
+                let (name, hue, saturation, l) = if *self == Notation::Hsluv {
+                    let hsluv: palette::Hsluv = color.color.into_color_unclamped();
+                    ("hsluv", hsluv.hue, hsluv.saturation, hsluv.l)
+                } else {
+                    let hpluv: Hpluv = color.color.into_color_unclamped();
+                    ("hpluv", hpluv.hue, hpluv.saturation, hpluv.l)
+                };
+                // black and white do not have a saturation
+                let saturation = if fixed(l) == 0.0 || fixed(l) == 100.0 {
+                    0.0
+                } else {
+                    saturation
+                };
+                let hue = polar_hue(saturation, hue.into_positive_degrees());
+                match alpha_position {
+                    AlphaPosition::End => format!(
+                        "{}({:.precision$}, {:.precision$}%, {:.precision$}%, {})",
+                        name,
+                        hue,
+                        fixed(saturation),
+                        fixed(l),
+                        pretty_percent(percent(color.alpha) / 100.0),
+                    ),
+                    _ => format!(
+                        "{}({:.precision$}, {:.precision$}%, {:.precision$}%)",
+                        name,
+                        hue,
+                        fixed(saturation),
+                        fixed(l),
+                    ),
+                }
+            }
+            Notation::Luv => {
+                let luv = color.to_luv(reference_white);
+                format!(
+                    "luv({:.precision$}, {:.precision$}, {:.precision$})",
+                    fixed(luv.l),
+                    fixed(luv.u),
+                    fixed(luv.v),
+                )
+            }
+            Notation::Lchuv => {
+                let lch: palette::Lchuv<Any> =
+                    color.to_luv(reference_white).color.into_color_unclamped();
+                format!(
+                    "lchuv({:.precision$}, {:.precision$}, {:.precision$})",
+                    fixed(lch.l),
+                    fixed(lch.chroma),
+                    polar_hue(lch.chroma, lch.hue.into_positive_degrees()),
+                )
+            }
+            Notation::Jzazbz => {
+                let jzazbz =
+                    Jzazbz::from_xyz(color.color.into_color_unclamped(), sdr_white_luminance);
+                format!(
+                    "jzazbz({:.fine_precision$} {:.fine_precision$} {:.fine_precision$}{})",
+                    fixed_fine(jzazbz.jz),
+                    fixed_fine(jzazbz.az),
+                    fixed_fine(jzazbz.bz),
+                    css_alpha,
+                )
+            }
+            Notation::Jzczhz => {
+                let jzczhz: Jzczhz =
+                    Jzazbz::from_xyz(color.color.into_color_unclamped(), sdr_white_luminance)
+                        .into_color_unclamped();
+                format!(
+                    "jzczhz({:.fine_precision$} {:.fine_precision$} {:.precision$}{})",
+                    fixed_fine(jzczhz.jz),
+                    fixed_fine(jzczhz.chroma),
+                    polar_hue(jzczhz.chroma, jzczhz.hue),
+                    css_alpha,
+                )
+            }
+            Notation::Ictcp => {
+                let ictcp =
+                    Ictcp::from_xyz(color.color.into_color_unclamped(), sdr_white_luminance);
+                format!(
+                    "ictcp({:.fine_precision$} {:.fine_precision$} {:.fine_precision$}{})",
+                    fixed_fine(ictcp.i),
+                    fixed_fine(ictcp.ct),
+                    fixed_fine(ictcp.cp),
+                    css_alpha,
+                )
+            }
+            Notation::Cam16 => {
+                let cam16 = color.to_cam16(viewing_conditions);
+                format!(
+                    "cam16({:.precision$}, {:.precision$}, {:.precision$})",
+                    fixed(cam16.lightness as f32),
+                    fixed(cam16.chroma as f32),
+                    polar_hue(
+                        cam16.chroma as f32,
+                        cam16.hue.into_positive_degrees() as f32
+                    ),
+                )
+            }
+            Notation::Cam16Ucs => {
+                let jab: Cam16UcsJab<f64> =
+                    Cam16Jmh::from_full(color.to_cam16(viewing_conditions)).into_color_unclamped();
+                format!(
+                    "cam16ucs({:.precision$}, {:.precision$}, {:.precision$})",
+                    fixed(jab.lightness as f32),
+                    fixed(jab.a as f32),
+                    fixed(jab.b as f32),
+                )
+            }
+            Notation::Ycbcr => {
+                let [y, cb, cr] = ycbcr_encoding.components(color);
+                format!("ycbcr({}, {}, {})", y, cb, cr)
+            }
+            Notation::Cct => match Cct::from_xyz(color.color.into_color_unclamped()) {
+                // Duv values are small, so they are commonly shown with four digits
+                Some(cct) => format!(
+                    "{}K, Duv {:.4}",
+                    round_half_up(cct.temperature),
+                    round_half_up(cct.duv * 10000.0) / 10000.0
+                ),
+                None => gettextrs::gettext("Undefined"),
+            },
+            Notation::LinearFloats | Notation::Floats => {
+                let [red, green, blue] = if *self == Notation::LinearFloats {
+                    let linear: palette::LinSrgb = color.color.into_color_unclamped();
+                    [linear.red, linear.green, linear.blue]
+                } else {
+                    [color.red, color.green, color.blue]
+                };
+                match alpha_position {
+                    AlphaPosition::End => format!(
+                        "{:.fine_precision$}, {:.fine_precision$}, {:.fine_precision$}, {:.fine_precision$}",
+                        fixed_fine(red),
+                        fixed_fine(green),
+                        fixed_fine(blue),
+                        fixed_fine(color.alpha),
+                    ),
+                    _ => format!(
+                        "{:.fine_precision$}, {:.fine_precision$}, {:.fine_precision$}",
+                        fixed_fine(red),
+                        fixed_fine(green),
+                        fixed_fine(blue),
+                    ),
+                }
+            }
+            // the alpha value is placed where the API expects it, so the alpha position
+            // only determines whether it is included
+            Notation::UiColor => format!(
+                "UIColor(red: {:.fine_precision$}, green: {:.fine_precision$}, blue: {:.fine_precision$}, alpha: {:.fine_precision$})",
+                fixed_fine(color.red),
+                fixed_fine(color.green),
+                fixed_fine(color.blue),
+                // the alpha value is required by UIKit
+                fixed_fine(if alpha_position == AlphaPosition::None {
+                    1.0
+                } else {
+                    color.alpha
+                }),
+            ),
+            Notation::SwiftUi => {
+                let opacity = match alpha_position {
+                    AlphaPosition::None => String::new(),
+                    _ => format!(", opacity: {:.fine_precision$}", fixed_fine(color.alpha)),
+                };
+                format!(
+                    "Color(red: {:.fine_precision$}, green: {:.fine_precision$}, blue: {:.fine_precision$}{})",
+                    fixed_fine(color.red),
+                    fixed_fine(color.green),
+                    fixed_fine(color.blue),
+                    opacity,
+                )
+            }
+            Notation::Compose | Notation::Flutter | Notation::AndroidXml => {
+                let [r, g, b, a] = color.rgba8().map(|value| format!("{:02X}", value));
+                // Android uses the ARGB order, opaque colors in code still need the alpha value
+                let argb = match (self, alpha_position) {
+                    (Notation::AndroidXml, AlphaPosition::None) => format!("{}{}{}", r, g, b),
+                    (_, AlphaPosition::None) => format!("FF{}{}{}", r, g, b),
+                    _ => format!("{}{}{}{}", a, r, g, b),
+                };
+                match self {
+                    Notation::Compose => format!("Color(0x{})", argb),
+                    Notation::Flutter => format!("const Color(0x{})", argb),
+                    _ => format!(
+                        "<color name=\"color_{}\">#{}</color>",
+                        format!("{}{}{}", r, g, b).to_lowercase(),
+                        argb
+                    ),
+                }
+            }
+            Notation::QColor | Notation::CSharp | Notation::JavaAwt => {
+                let [r, g, b, a] = color.rgba8();
+                let name = match self {
+                    Notation::QColor => "QColor",
+                    Notation::CSharp => "Color.FromArgb",
+                    _ => "new Color",
+                };
+                match (self, alpha_position) {
+                    (_, AlphaPosition::None) => format!("{}({}, {}, {})", name, r, g, b),
+                    (Notation::CSharp, _) => format!("{}({}, {}, {}, {})", name, a, r, g, b),
+                    _ => format!("{}({}, {}, {}, {})", name, r, g, b, a),
+                }
+            }
+            Notation::Unity => {
+                let alpha = match alpha_position {
+                    AlphaPosition::None => String::new(),
+                    _ => format!(", {:.fine_precision$}f", fixed_fine(color.alpha)),
+                };
+                format!(
+                    "new Color({:.fine_precision$}f, {:.fine_precision$}f, {:.fine_precision$}f{})",
+                    fixed_fine(color.red),
+                    fixed_fine(color.green),
+                    fixed_fine(color.blue),
+                    alpha,
+                )
+            }
+            Notation::Glsl => match alpha_position {
+                AlphaPosition::None => format!(
+                    "vec3({:.fine_precision$}, {:.fine_precision$}, {:.fine_precision$})",
+                    fixed_fine(color.red),
+                    fixed_fine(color.green),
+                    fixed_fine(color.blue),
+                ),
+                _ => format!(
+                    "vec4({:.fine_precision$}, {:.fine_precision$}, {:.fine_precision$}, {:.fine_precision$})",
+                    fixed_fine(color.red),
+                    fixed_fine(color.green),
+                    fixed_fine(color.blue),
+                    fixed_fine(color.alpha),
+                ),
+            },
+            Notation::DecimalInt => packed::rgb(color, alpha_position).to_string(),
+            Notation::HexInt => match alpha_position {
+                AlphaPosition::None => format!("0x{:06X}", packed::rgb(color, alpha_position)),
+                _ => format!("0x{:08X}", packed::rgb(color, alpha_position)),
+            },
+            Notation::Colorref => format!("0x{:08X}", packed::colorref(color)),
+            // the decoded color shows how much is lost by the quantization
+            Notation::Rgb565 => {
+                let value = packed::rgb565(color);
+                let decoded = Notation::Hex.as_str(packed::from_rgb565(value), options);
+                format!("0x{:04X} ({})", value, decoded)
+            }
+            Notation::Rgb555 => {
+                let value = packed::rgb555(color, alpha_position);
+                let decoded =
+                    Notation::Hex.as_str(packed::from_rgb555(value, alpha_position), options);
+                format!("0x{:04X} ({})", value, decoded)
+            }
+            Notation::Name => Self::format_name(color, options).0,
         }
     }
 
+    /// Formats the color as the name of the closest named color, returning the match as well,
+    /// so it does not have to be searched again, such as to show how close it is.
+    pub fn format_name(color: Color, options: &NotationOptions) -> (String, Option<NameMatch>) {
+        let found = color_names::nearest(
+            color,
+            options.name_sources,
+            &options.user_name_sources,
+            options.name_tolerance,
+        );
+        let name = found.map_or_else(
+            || gettextrs::gettext("Not named"),
+            |found| found.name.to_owned(),
+        );
+        (name, found)
+    }
+
+    /// Whether the notation is limited to the sRGB gamut.
+    ///
+    /// Colors outside of the gamut will be clamped when displayed in these notations.
+    pub fn is_limited_to_srgb(&self) -> bool {
+        matches!(
+            self,
+            Notation::Hex
+                | Notation::Rgb
+                | Notation::Hsl
+                | Notation::Hsv
+                | Notation::Cmyk
+                | Notation::Hwb
+                | Notation::Name
+                | Notation::Compose
+                | Notation::Flutter
+                | Notation::AndroidXml
+                | Notation::QColor
+                | Notation::CSharp
+                | Notation::JavaAwt
+                | Notation::DecimalInt
+                | Notation::HexInt
+                | Notation::Colorref
+                | Notation::Rgb565
+                | Notation::Rgb555
+        )
+    }
+
     pub fn display_copy_string(&self) -> String {
         gettextrs::gettext(match self {
             Notation::Hex => "Copy Hex Code",
@@ -240,6 +1131,7 @@ impl Notation {
             Notation::Hsv => "Copy HSV",
             Notation::Cmyk => "Copy CMYK",
             Notation::Xyz => "Copy XYZ",
+            Notation::Xyy => "Copy xyY",
             Notation::Lab => "Copy CIELAB",
             Notation::Hwb => "Copy HWB",
             Notation::Hcl => "Copy CIELCh / HCL",
@@ -247,30 +1139,110 @@ impl Notation {
             Notation::HunterLab => "Copy Hunter Lab",
             Notation::Oklab => "Copy Oklab",
             Notation::Oklch => "Copy Oklch",
+            Notation::Okhsl => "Copy OKHSL",
+            Notation::Okhsv => "Copy OKHSV",
+            Notation::DisplayP3 => "Copy Display P3",
+            Notation::Rec2020 => "Copy Rec. 2020",
+            Notation::AdobeRgb => "Copy Adobe RGB",
+            Notation::ProPhotoRgb => "Copy ProPhoto RGB",
+            Notation::CssColor => "Copy CSS Color",
+            Notation::CssLab => "Copy CSS lab()",
+            Notation::CssLch => "Copy CSS lch()",
+            Notation::Hsluv => "Copy HSLuv",
+            Notation::Hpluv => "Copy HPLuv",
+            Notation::Luv => "Copy CIELUV",
+            Notation::Lchuv => "Copy CIELCh(uv)",
+            Notation::Jzazbz => "Copy Jzazbz",
+            Notation::Jzczhz => "Copy JzCzhz",
+            Notation::Ictcp => "Copy ICtCp",
+            Notation::Cam16 => "Copy CAM16",
+            Notation::Cam16Ucs => "Copy CAM16-UCS",
+            Notation::Ycbcr => "Copy YCbCr",
+            Notation::Cct => "Copy Color Temperature",
+            Notation::LinearFloats => "Copy Linear sRGB Floats",
+            Notation::Floats => "Copy sRGB Floats",
+            Notation::UiColor => "Copy Swift UIColor",
+            Notation::SwiftUi => "Copy SwiftUI",
+            Notation::Compose => "Copy Jetpack Compose",
+            Notation::Flutter => "Copy Flutter",
+            Notation::AndroidXml => "Copy Android XML",
+            Notation::QColor => "Copy Qt QColor",
+            Notation::CSharp => "Copy C# Color",
+            Notation::JavaAwt => "Copy Java AWT",
+            Notation::Unity => "Copy Unity",
+            Notation::Glsl => "Copy GLSL",
+            Notation::DecimalInt => "Copy Decimal Integer",
+            Notation::HexInt => "Copy Hex Integer",
+            Notation::Colorref => "Copy COLORREF",
+            Notation::Rgb565 => "Copy RGB565",
+            Notation::Rgb555 => "Copy RGB555",
             Notation::Name => "Copy Name",
         })
     }
 
+    /// The name of the notation shown to the user.
+    pub fn label(&self) -> String {
+        match self {
+            Notation::Hex => gettextrs::gettext("Hex Code"),
+            Notation::Rgb => "RGB".to_string(),
+            Notation::Hsl => "HSL".to_string(),
+            Notation::Hsv => "HSV".to_string(),
+            Notation::Cmyk => "CMYK".to_string(),
+            Notation::Xyz => "XYZ".to_string(),
+            Notation::Xyy => "xyY".to_string(),
+            Notation::Lab => "CIELAB".to_string(),
+            Notation::Hwb => "HWB".to_string(),
+            Notation::Hcl => "CIELCh / HCL".to_string(),
+            Notation::Lms => "LMS".to_string(),
+            Notation::HunterLab => "Hunter Lab".to_string(),
+            Notation::Oklab => "Oklab".to_string(),
+            Notation::Oklch => "Oklch".to_string(),
+            Notation::Okhsl => "OKHSL".to_string(),
+            Notation::Okhsv => "OKHSV".to_string(),
+            Notation::DisplayP3 => "Display P3".to_string(),
+            Notation::Rec2020 => "Rec. 2020".to_string(),
+            Notation::AdobeRgb => "Adobe RGB (1998)".to_string(),
+            Notation::ProPhotoRgb => "ProPhoto RGB".to_string(),
+            Notation::CssColor => "CSS color()".to_string(),
+            Notation::CssLab => "CSS lab()".to_string(),
+            Notation::CssLch => "CSS lch()".to_string(),
+            Notation::Hsluv => "HSLuv".to_string(),
+            Notation::Hpluv => "HPLuv".to_string(),
+            Notation::Luv => "CIELUV".to_string(),
+            Notation::Lchuv => "CIELCh(uv)".to_string(),
+            Notation::Jzazbz => "Jzazbz".to_string(),
+            Notation::Jzczhz => "JzCzhz".to_string(),
+            Notation::Ictcp => "ICtCp".to_string(),
+            Notation::Cam16 => "CAM16".to_string(),
+            Notation::Cam16Ucs => "CAM16-UCS".to_string(),
+            Notation::Ycbcr => "YCbCr".to_string(),
+            Notation::Cct => gettextrs::gettext("Color Temperature"),
+            Notation::LinearFloats => gettextrs::gettext("Linear sRGB Floats"),
+            Notation::Floats => gettextrs::gettext("sRGB Floats"),
+            Notation::UiColor => "Swift UIColor".to_string(),
+            Notation::SwiftUi => "SwiftUI".to_string(),
+            Notation::Compose => "Jetpack Compose".to_string(),
+            Notation::Flutter => "Flutter".to_string(),
+            Notation::AndroidXml => "Android XML".to_string(),
+            Notation::QColor => "Qt QColor".to_string(),
+            Notation::CSharp => "C# Color".to_string(),
+            Notation::JavaAwt => "Java AWT".to_string(),
+            Notation::Unity => "Unity".to_string(),
+            Notation::Glsl => "GLSL".to_string(),
+            Notation::DecimalInt => gettextrs::gettext("Decimal Integer"),
+            Notation::HexInt => gettextrs::gettext("Hex Integer"),
+            Notation::Colorref => "COLORREF".to_string(),
+            Notation::Rgb565 => "RGB565".to_string(),
+            Notation::Rgb555 => "RGB555".to_string(),
+            Notation::Name => "Name".to_string(),
+        }
+    }
+
     pub fn to_color_format_object(self, identifier: String, color: Color) -> ColorFormatObject {
         ColorFormatObject::new(
             identifier,
-            match self {
-                Notation::Hex => gettextrs::gettext("Hex Code"),
-                Notation::Rgb => "RGB".to_string(),
-                Notation::Hsl => "HSL".to_string(),
-                Notation::Hsv => "HSV".to_string(),
-                Notation::Cmyk => "CMYK".to_string(),
-                Notation::Xyz => "XYZ".to_string(),
-                Notation::Lab => "CIELAB".to_string(),
-                Notation::Hwb => "HWB".to_string(),
-                Notation::Hcl => "CIELCh / HCL".to_string(),
-                Notation::Lms => "LMS".to_string(),
-                Notation::HunterLab => "Hunter Lab".to_string(),
-                Notation::Oklab => "Oklab".to_string(),
-                Notation::Oklch => "Oklch".to_string(),
-                Notation::Name => "Name".to_string(),
-            },
-            self.as_str(color, AlphaPosition::None, 2, ColorNameSources::empty()),
+            self.label(),
+            self.as_str(color, &NotationOptions::default()),
         )
     }
 }
@@ -286,6 +1258,7 @@ impl FromStr for Notation {
             "hsv" => Self::Hsv,
             "cmyk" => Self::Cmyk,
             "xyz" => Self::Xyz,
+            "xyy" => Self::Xyy,
             "cielab" => Self::Lab,
             "hwb" => Self::Hwb,
             "hcl" => Self::Hcl,
@@ -294,6 +1267,43 @@ impl FromStr for Notation {
             "hunterlab" => Self::HunterLab,
             "oklab" => Self::Oklab,
             "oklch" => Self::Oklch,
+            "okhsl" => Self::Okhsl,
+            "okhsv" => Self::Okhsv,
+            "displayp3" => Self::DisplayP3,
+            "rec2020" => Self::Rec2020,
+            "adobergb" => Self::AdobeRgb,
+            "prophotorgb" => Self::ProPhotoRgb,
+            "csscolor" => Self::CssColor,
+            "csslab" => Self::CssLab,
+            "csslch" => Self::CssLch,
+            "hsluv" => Self::Hsluv,
+            "hpluv" => Self::Hpluv,
+            "cieluv" => Self::Luv,
+            "lchuv" => Self::Lchuv,
+            "jzazbz" => Self::Jzazbz,
+            "jzczhz" => Self::Jzczhz,
+            "ictcp" => Self::Ictcp,
+            "cam16" => Self::Cam16,
+            "cam16ucs" => Self::Cam16Ucs,
+            "ycbcr" => Self::Ycbcr,
+            "cct" => Self::Cct,
+            "linearfloats" => Self::LinearFloats,
+            "floats" => Self::Floats,
+            "uicolor" => Self::UiColor,
+            "swiftui" => Self::SwiftUi,
+            "compose" => Self::Compose,
+            "flutter" => Self::Flutter,
+            "androidxml" => Self::AndroidXml,
+            "qcolor" => Self::QColor,
+            "csharp" => Self::CSharp,
+            "javaawt" => Self::JavaAwt,
+            "unity" => Self::Unity,
+            "glsl" => Self::Glsl,
+            "decimal" => Self::DecimalInt,
+            "hexint" => Self::HexInt,
+            "colorref" => Self::Colorref,
+            "rgb565" => Self::Rgb565,
+            "rgb555" => Self::Rgb555,
             _ => {
                 log::error!("Failed to parse notation: {}", s);
                 return Err(ColorError::ParsingError(
@@ -303,3 +1313,458 @@ impl FromStr for Notation {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTATIONS: [Notation; 52] = Notation::ALL;
+
+    /// Formats the color, parses the result and formats it again, returning both strings.
+    fn round_trip(
+        notation: Notation,
+        color: Color,
+        alpha_position: AlphaPosition,
+        precision: usize,
+    ) -> (String, String) {
+        let options = NotationOptions {
+            alpha_position,
+            precision,
+            name_sources: ColorNameSources::all(),
+            ..Default::default()
+        };
+        let format = |color| notation.as_str(color, &options);
+
+        let formatted = format(color);
+        let parsed = notation
+            .parse_with(&formatted, &options)
+            .unwrap_or_else(|_| panic!("{notation:?}: failed to parse `{formatted}`"));
+        (formatted, format(parsed))
+    }
+
+    /// Asserts that both strings only differ in the last displayed digit of their values,
+    /// which can change when rounding the floating point errors of the conversions.
+    fn assert_within_precision(expected: &str, actual: &str, precision: usize) {
+        let numbers = |string: &str| {
+            string
+                .split(|char: char| !(char.is_ascii_digit() || char == '.' || char == '-'))
+                .filter_map(|number| number.parse::<f32>().ok())
+                .collect::<Vec<_>>()
+        };
+
+        let tolerance = 1.5 / 10f32.powi(precision as i32);
+        let (expected_numbers, actual_numbers) = (numbers(expected), numbers(actual));
+        assert!(
+            expected_numbers.len() == actual_numbers.len()
+                && expected_numbers
+                    .iter()
+                    .zip(&actual_numbers)
+                    .all(|(expected, actual)| (expected - actual).abs() <= tolerance),
+            "expected `{expected}`, got `{actual}`"
+        );
+    }
+
+    #[test]
+    fn it_round_trips_within_displayed_precision() {
+        let colors = [
+            Color::rgb(46, 52, 64),
+            Color::rgb(255, 255, 255),
+            Color::new(0.623, 0.1, 0.9, 1.0),
+            Color::new(0.18, 0.62, 0.95, 0.5),
+            Color::new(0.9, 0.45, 0.05, 0.0),
+        ];
+
+        // names and color temperatures are only defined for some colors
+        for notation in NOTATIONS
+            .into_iter()
+            .filter(|n| !matches!(n, Notation::Name | Notation::Cct))
+        {
+            for color in colors {
+                for alpha_position in [AlphaPosition::None, AlphaPosition::End] {
+                    for precision in [2, 3] {
+                        let (formatted, round_tripped) =
+                            round_trip(notation, color, alpha_position, precision);
+                        assert_within_precision(&formatted, &round_tripped, precision);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_round_trips_names() {
+        let (formatted, round_tripped) = round_trip(
+            Notation::Name,
+            Color::rgb(255, 0, 0),
+            AlphaPosition::None,
+            2,
+        );
+        assert_eq!(formatted, round_tripped);
+    }
+
+    #[test]
+    fn it_round_trips_color_temperatures() {
+        for color in [
+            Color::rgb(255, 255, 255),
+            Color::rgb(255, 197, 143),
+            Color::rgb(201, 226, 255),
+        ] {
+            let (formatted, round_tripped) =
+                round_trip(Notation::Cct, color, AlphaPosition::None, 2);
+            assert_eq!(formatted, round_tripped);
+        }
+    }
+
+    #[test]
+    fn it_formats_undefined_color_temperatures() {
+        let options = NotationOptions::default();
+        assert_eq!(
+            "6505K, Duv 0.0033",
+            Notation::Cct.as_str(Color::rgb(255, 255, 255), &options)
+        );
+        assert_eq!(
+            "Undefined",
+            Notation::Cct.as_str(Color::rgb(0, 255, 0), &options)
+        );
+    }
+
+    #[test]
+    fn it_keeps_full_precision() {
+        let color = Notation::Oklch
+            .parse_with("oklch(62.3% 0.1 250)", &NotationOptions::default())
+            .unwrap();
+        assert_eq!(
+            "oklch(62.30% 0.10 250.00)",
+            Notation::Oklch.as_str(color, &NotationOptions::default())
+        );
+    }
+
+    #[test]
+    fn it_rounds_half_up() {
+        // 0.5 * 255 = 127.5
+        let gray = Color::new(0.5, 0.5, 0.5, 1.0);
+        let format = |notation: Notation| notation.as_str(gray, &NotationOptions::default());
+
+        assert_eq!("#808080", format(Notation::Hex));
+        assert_eq!("rgb(128, 128, 128)", format(Notation::Rgb));
+    }
+
+    #[test]
+    fn it_uses_d50_for_css_lab() {
+        let red = Color::rgb(255, 0, 0);
+        // the reference white only applies to the colorimetric notations
+        let options = NotationOptions {
+            reference_white: ReferenceWhite::new(Illuminant::A, Observer::Degree10),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "lab(54.29% 80.81 69.89)",
+            Notation::CssLab.as_str(red, &options)
+        );
+        assert_eq!(
+            "lch(54.29% 106.84 40.85)",
+            Notation::CssLch.as_str(red, &options)
+        );
+        assert_eq!(
+            "cielab(53.24, 80.09, 67.20)",
+            Notation::Lab.as_str(red, &NotationOptions::default())
+        );
+    }
+
+    #[test]
+    fn it_formats_floats_with_two_more_digits() {
+        let color = Color::rgba(46, 52, 64, 128);
+        let options = NotationOptions {
+            alpha_position: AlphaPosition::End,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "0.0273, 0.0343, 0.0513, 0.5020",
+            Notation::LinearFloats.as_str(color, &options)
+        );
+        assert_eq!(
+            "0.1804, 0.2039, 0.2510",
+            Notation::Floats.as_str(color, &NotationOptions::default())
+        );
+    }
+
+    #[test]
+    fn it_places_the_alpha_value_of_code_literals_where_the_api_expects_it() {
+        let color = Color::rgba(46, 52, 64, 128);
+        let format = |notation: Notation, alpha_position| {
+            notation.as_str(
+                color,
+                &NotationOptions {
+                    alpha_position,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(
+            "Color(0xFF2E3440)",
+            format(Notation::Compose, AlphaPosition::None)
+        );
+        assert_eq!(
+            "Color(0x802E3440)",
+            format(Notation::Compose, AlphaPosition::End)
+        );
+        assert_eq!(
+            r#"<color name="color_2e3440">#2E3440</color>"#,
+            format(Notation::AndroidXml, AlphaPosition::None)
+        );
+        assert_eq!(
+            "Color.FromArgb(128, 46, 52, 64)",
+            format(Notation::CSharp, AlphaPosition::End)
+        );
+        assert_eq!(
+            "QColor(46, 52, 64, 128)",
+            format(Notation::QColor, AlphaPosition::Start)
+        );
+    }
+
+    #[test]
+    fn it_uses_the_sdr_white_luminance() {
+        let white = Color::rgb(255, 255, 255);
+        let format = |sdr_white_luminance| {
+            Notation::Ictcp.as_str(
+                white,
+                &NotationOptions {
+                    sdr_white_luminance,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!("ictcp(0.5081 0.0000 0.0000)", format(100.0));
+        assert_eq!("ictcp(0.7518 0.0000 0.0000)", format(1000.0));
+    }
+
+    #[test]
+    fn it_uses_the_viewing_conditions() {
+        let color = Color::rgb(46, 52, 64);
+        let format = |viewing_conditions| {
+            Notation::Cam16.as_str(
+                color,
+                &NotationOptions {
+                    viewing_conditions,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(
+            "cam16(15.53, 11.97, 262.80)",
+            format(ViewingConditions::default())
+        );
+        assert_eq!(
+            "cam16(20.21, 11.66, 261.46)",
+            format(ViewingConditions {
+                adapting_luminance: 200.0,
+                background_luminance: 0.5,
+                surround: Surround::Dark,
+            })
+        );
+    }
+
+    #[test]
+    fn it_shows_the_quantization_of_16_bit_colors() {
+        let color = Color::rgb(46, 52, 64);
+        let options = NotationOptions::default();
+
+        assert_eq!("0x31A8 (#313542)", Notation::Rgb565.as_str(color, &options));
+        assert_eq!("0x18C8 (#313142)", Notation::Rgb555.as_str(color, &options));
+        assert_eq!("0x2E3440", Notation::HexInt.as_str(color, &options));
+        assert_eq!("3028032", Notation::DecimalInt.as_str(color, &options));
+    }
+
+    #[test]
+    fn it_formats_css_functions_in_the_chosen_syntax() {
+        let color = Color::rgba(46, 52, 64, 128);
+        let modern = NotationOptions {
+            alpha_position: AlphaPosition::End,
+            css_style: CssStyle {
+                rgb: CssSyntax::Modern,
+                hsl: CssSyntax::Modern,
+                hwb: CssSyntax::Modern,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!("rgb(46 52 64 / 0.50)", Notation::Rgb.as_str(color, &modern));
+        assert_eq!(
+            "hsl(220 16% 22% / 0.50)",
+            Notation::Hsl.as_str(color, &modern)
+        );
+        assert_eq!(
+            "hwb(220 18% 75% / 0.50)",
+            Notation::Hwb.as_str(color, &modern)
+        );
+        assert_eq!(
+            "rgba(46, 52, 64, 0.50)",
+            Notation::Rgb.as_str(
+                color,
+                &NotationOptions {
+                    alpha_position: AlphaPosition::End,
+                    ..Default::default()
+                }
+            )
+        );
+
+        for notation in [Notation::Rgb, Notation::Hsl, Notation::Hwb] {
+            let formatted = notation.as_str(color, &modern);
+            let parsed = notation.parse_with(&formatted, &modern).unwrap();
+            assert_eq!(formatted, notation.as_str(parsed, &modern));
+        }
+    }
+
+    #[test]
+    fn it_formats_css_colors_in_the_chosen_space() {
+        let color = Color::rgb(255, 0, 0);
+        let options = |color_space| NotationOptions {
+            css_style: CssStyle {
+                color_space,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        for (color_space, expected) in [
+            (PredefinedSpace::Srgb, "color(srgb 1.00 0.00 0.00)"),
+            (
+                PredefinedSpace::SrgbLinear,
+                "color(srgb-linear 1.00 0.00 0.00)",
+            ),
+            (PredefinedSpace::XyzD50, "color(xyz-d50 0.44 0.22 0.01)"),
+            (PredefinedSpace::XyzD65, "color(xyz-d65 0.41 0.21 0.02)"),
+            (
+                PredefinedSpace::Rgb(RgbSpace::DisplayP3),
+                "color(display-p3 0.92 0.20 0.14)",
+            ),
+        ] {
+            let options = options(color_space);
+            let formatted = Notation::CssColor.as_str(color, &options);
+            assert_eq!(expected, formatted);
+
+            let parsed = Notation::CssColor.parse_with(&formatted, &options).unwrap();
+            assert_eq!(formatted, Notation::CssColor.as_str(parsed, &options));
+        }
+    }
+
+    #[test]
+    fn it_detects_the_notation() {
+        let options = NotationOptions::default();
+        let detect = |input| Notation::detect_with(input, &Notation::ALL, &options).unwrap();
+
+        let detected = detect("  rgb(46 52 64) ");
+        assert_eq!(Notation::Rgb, detected.notation);
+        assert_eq!(Color::rgb(46, 52, 64), detected.color);
+
+        assert_eq!(Notation::Name, detect("cornflowerblue").notation);
+        assert_eq!(Notation::Compose, detect("Color(0xFF2E3440)").notation);
+        assert_eq!(
+            Notation::DisplayP3,
+            detect("color(display-p3 1 0 0)").notation
+        );
+        assert!(!detect("color(display-p3 1 0 0)").is_ambiguous());
+        assert!(Notation::detect_with("not a color", &Notation::ALL, &options).is_none());
+    }
+
+    #[test]
+    fn it_detects_only_unmistakable_notations_in_text() {
+        let options = NotationOptions::default();
+        let detect = |input| Notation::detect_with(input, &Notation::unmistakable(input), &options);
+
+        assert!(detect("123456").is_none());
+        assert!(detect("2024").is_none());
+        assert!(detect("6500K").is_none());
+        assert_eq!(Notation::Hex, detect("#123456").unwrap().notation);
+        assert_eq!(Notation::Rgb, detect("rgb(46 52 64)").unwrap().notation);
+        assert_eq!(Notation::Name, detect("cornflowerblue").unwrap().notation);
+    }
+
+    #[test]
+    fn it_reports_ambiguous_input() {
+        let detected =
+            Notation::detect_with("123456", &Notation::ALL, &NotationOptions::default()).unwrap();
+        assert_eq!(Notation::Hex, detected.notation);
+        assert!(detected
+            .alternatives
+            .iter()
+            .any(|(notation, _)| *notation == Notation::DecimalInt));
+    }
+
+    #[test]
+    fn it_explains_what_was_expected() {
+        let options = NotationOptions::default();
+
+        let diagnostic = Notation::Hsl
+            .parse_with("hsl(220, 16x, 22%)", &options)
+            .unwrap_err();
+        assert_eq!(11..12, diagnostic.span);
+        assert_eq!(
+            DiagnosticKind::Expected(Expectation::CssPercentage),
+            diagnostic.kind
+        );
+
+        let diagnostic = Notation::Rgb
+            .parse_with("rgb(46, 52", &options)
+            .unwrap_err();
+        assert!(diagnostic.span.is_empty());
+        assert_eq!(
+            DiagnosticKind::Expected(Expectation::RgbValue),
+            diagnostic.kind
+        );
+    }
+
+    #[test]
+    fn it_reports_clamped_and_ignored_values() {
+        let options = NotationOptions::default();
+
+        let (color, diagnostics) = Notation::Rgb
+            .parse_diagnosed_with("rgb(300, 52, 64) extra", &options)
+            .unwrap();
+        assert_eq!(Color::rgb(255, 52, 64), color);
+        assert_eq!(
+            vec![
+                Diagnostic::new("rgb(300, 52, 64) extra", 4..7, DiagnosticKind::Clamped),
+                Diagnostic::new("rgb(300, 52, 64) extra", 17..22, DiagnosticKind::Ignored),
+            ],
+            diagnostics
+        );
+
+        let (_, diagnostics) = Notation::Rgb
+            .parse_diagnosed_with(" rgb(46, 52, 64) ", &options)
+            .unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn it_shows_approximate_names() {
+        let options = NotationOptions {
+            name_sources: ColorNameSources::Html,
+            ..Default::default()
+        };
+
+        // only the name is shown, so that it can be copied as it is
+        let (formatted, found) = Notation::format_name(Color::rgb(250, 5, 3), &options);
+        assert_eq!("red", formatted);
+        assert!(!found.unwrap().is_exact());
+        assert_eq!(
+            formatted,
+            Notation::Name.as_str(Color::rgb(250, 5, 3), &options)
+        );
+
+        let options = NotationOptions {
+            name_tolerance: 0.5,
+            ..options
+        };
+        assert_eq!(
+            "Not named",
+            Notation::Name.as_str(Color::rgb(250, 5, 3), &options)
+        );
+    }
+}
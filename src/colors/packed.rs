@@ -0,0 +1,150 @@
+use super::{
+    color::{round_half_up, Color},
+    position::AlphaPosition,
+};
+
+/// Reduces a component of the color to the given number of bits.
+fn quantize(value: f32, bits: u32) -> u16 {
+    let max = (1 << bits) - 1;
+    round_half_up(value.clamp(0.0, 1.0) * max as f32) as u16
+}
+
+/// Expands a component with the given number of bits to 8 bits.
+fn expand(value: u16, bits: u32) -> u8 {
+    let max = (1 << bits) - 1;
+    round_half_up((value & max) as f32 * 255.0 / max as f32) as u8
+}
+
+/// Returns the color as a 32-bit integer, with the alpha value at the given position.
+///
+/// Without an alpha value, only the lower 24 bits are used.
+pub fn rgb(color: Color, alpha_position: AlphaPosition) -> u32 {
+    let [red, green, blue, alpha] = color.rgba8();
+    match alpha_position {
+        AlphaPosition::None => u32::from_be_bytes([0, red, green, blue]),
+        AlphaPosition::Start => u32::from_be_bytes([alpha, red, green, blue]),
+        AlphaPosition::End => u32::from_be_bytes([red, green, blue, alpha]),
+    }
+}
+
+/// Creates a color from a 32-bit integer, with the alpha value at the given position.
+///
+/// Returns `None` if the value uses more than 24 bits without an alpha value.
+pub fn from_rgb(value: u32, alpha_position: AlphaPosition) -> Option<Color> {
+    let [first, second, third, fourth] = value.to_be_bytes();
+    match alpha_position {
+        AlphaPosition::None if first != 0 => None,
+        AlphaPosition::None => Some(Color::rgb(second, third, fourth)),
+        AlphaPosition::Start => Some(Color::rgba(second, third, fourth, first)),
+        AlphaPosition::End => Some(Color::rgba(first, second, third, fourth)),
+    }
+}
+
+/// Returns the color as a Win32 `COLORREF`, which stores the components in the `0x00BBGGRR` order.
+pub fn colorref(color: Color) -> u32 {
+    let [red, green, blue, _] = color.rgba8();
+    u32::from_le_bytes([red, green, blue, 0])
+}
+
+/// Creates a color from a Win32 `COLORREF`, ignoring the unused highest byte.
+pub fn from_colorref(value: u32) -> Color {
+    let [red, green, blue, _] = value.to_le_bytes();
+    Color::rgb(red, green, blue)
+}
+
+/// Returns the color as 16-bit RGB565, using 5 bits for red and blue and 6 bits for green.
+pub fn rgb565(color: Color) -> u16 {
+    (quantize(color.red, 5) << 11) | (quantize(color.green, 6) << 5) | quantize(color.blue, 5)
+}
+
+/// Creates a color from 16-bit RGB565.
+pub fn from_rgb565(value: u16) -> Color {
+    Color::rgb(
+        expand(value >> 11, 5),
+        expand(value >> 5, 6),
+        expand(value, 5),
+    )
+}
+
+/// Returns the color as 16-bit RGB555, using 5 bits for each component.
+///
+/// The remaining bit stores whether the color is opaque, at the given alpha position,
+/// which results in ARGB1555 or RGBA5551.
+pub fn rgb555(color: Color, alpha_position: AlphaPosition) -> u16 {
+    let rgb =
+        (quantize(color.red, 5) << 10) | (quantize(color.green, 5) << 5) | quantize(color.blue, 5);
+    let alpha = quantize(color.alpha, 1);
+    match alpha_position {
+        AlphaPosition::None => rgb,
+        AlphaPosition::Start => (alpha << 15) | rgb,
+        AlphaPosition::End => (rgb << 1) | alpha,
+    }
+}
+
+/// Creates a color from 16-bit RGB555, with the alpha bit at the given position.
+pub fn from_rgb555(value: u16, alpha_position: AlphaPosition) -> Color {
+    let (rgb, alpha) = match alpha_position {
+        AlphaPosition::None => (value, 1),
+        AlphaPosition::Start => (value, value >> 15),
+        AlphaPosition::End => (value >> 1, value & 1),
+    };
+    Color::rgba(
+        expand(rgb >> 10, 5),
+        expand(rgb >> 5, 5),
+        expand(rgb, 5),
+        expand(alpha, 1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_rgb() {
+        let red = Color::rgb(255, 0, 0);
+        assert_eq!(16711680, rgb(red, AlphaPosition::None));
+        assert_eq!(0xFFFF0000, rgb(red, AlphaPosition::Start));
+        assert_eq!(0xFF0000FF, rgb(red, AlphaPosition::End));
+
+        assert_eq!(Some(red), from_rgb(0xFFFF0000, AlphaPosition::Start));
+        assert_eq!(None, from_rgb(0xFFFF0000, AlphaPosition::None));
+    }
+
+    #[test]
+    fn packs_colorref() {
+        let color = Color::rgb(46, 52, 64);
+        assert_eq!(0x0040342E, colorref(color));
+        assert_eq!(color, from_colorref(0xFF40342E));
+    }
+
+    #[test]
+    fn quantizes_rgb565() {
+        assert_eq!(0xF800, rgb565(Color::rgb(255, 0, 0)));
+        assert_eq!(0xFFFF, rgb565(Color::rgb(255, 255, 255)));
+
+        let color = Color::rgb(46, 52, 64);
+        assert_eq!(0x31A8, rgb565(color));
+        assert_eq!([49, 53, 66, 255], from_rgb565(0x31A8).rgba8());
+    }
+
+    #[test]
+    fn quantizes_rgb555() {
+        let color = Color::rgba(46, 52, 64, 255);
+        assert_eq!(0x18C8, rgb555(color, AlphaPosition::None));
+        assert_eq!(0x98C8, rgb555(color, AlphaPosition::Start));
+        assert_eq!(0x3191, rgb555(color, AlphaPosition::End));
+
+        for alpha_position in [
+            AlphaPosition::None,
+            AlphaPosition::Start,
+            AlphaPosition::End,
+        ] {
+            let value = rgb555(color, alpha_position);
+            assert_eq!(
+                [49, 49, 66, 255],
+                from_rgb555(value, alpha_position).rgba8()
+            );
+        }
+    }
+}
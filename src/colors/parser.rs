@@ -1,18 +1,158 @@
+use std::{cell::RefCell, ops::Range};
+
 use nom::{
     branch::alt,
-    bytes::complete::{tag, tag_no_case, take_while_m_n},
+    bytes::complete::{tag, tag_no_case, take_while, take_while_m_n},
     character::{
-        complete::{digit0, digit1, multispace0},
+        complete::{digit0, digit1, multispace0, satisfy},
         is_hex_digit,
     },
-    combinator::{map, map_res, opt, recognize, value},
-    error::ParseError,
+    combinator::{cut, map, map_opt, map_res, not, opt, value, verify},
+    error::{ErrorKind, FromExternalError, ParseError},
     multi::many_m_n,
-    sequence::{delimited, pair, separated_pair, terminated, Tuple},
-    AsChar, IResult, InputTakeAtPosition, Parser,
+    number::complete::recognize_float,
+    sequence::{delimited, pair, preceded, separated_pair, terminated, Tuple},
+    AsChar, InputLength, InputTakeAtPosition, Parser,
+};
+use palette::{
+    cam16::{Cam16Jch, Cam16Jmh, Cam16UcsJab},
+    convert::IntoColorUnclamped,
+    LinSrgba, WithAlpha,
+};
+
+use super::{
+    cct::{self, Cct},
+    cmyk::Cmyka,
+    color::Color,
+    css::PredefinedSpace,
+    diagnostic::Expectation,
+    hpluv::Hpluv,
+    hunterlab::HunterLab,
+    ictcp::Ictcp,
+    illuminant::ReferenceWhite,
+    jzazbz::{Jzazbz, Jzczhz},
+    packed,
+    position::AlphaPosition,
+    rgb_space::RgbSpace,
+    viewing_conditions::ViewingConditions,
+    ycbcr::YcbcrEncoding,
 };
 
-use super::{cmyk::Cmyka, color::Color, hunterlab::HunterLab, position::AlphaPosition};
+/// The error of the parsers, which remembers what was expected where the input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError<I> {
+    /// The remaining input, starting where the error occurred.
+    pub input: I,
+    pub kind: ErrorKind,
+    /// What was expected instead, such as a hue.
+    pub expected: Option<Expectation>,
+}
+
+impl<I: InputLength> ParseError<I> for SyntaxError<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            kind,
+            expected: None,
+        }
+    }
+
+    fn append(_input: I, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    /// Keeps the error of the alternative that got further, as it is the most specific one.
+    fn or(self, other: Self) -> Self {
+        if self.input.input_len() < other.input.input_len() {
+            self
+        } else {
+            other
+        }
+    }
+}
+
+impl<I, E> FromExternalError<I, E> for SyntaxError<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _error: E) -> Self {
+        Self {
+            input,
+            kind,
+            expected: None,
+        }
+    }
+}
+
+pub type IResult<I, O, E = SyntaxError<I>> = nom::IResult<I, O, E>;
+
+/// Describes what the parser expects, if it fails without a more specific expectation.
+///
+/// The innermost expectation is kept, as it describes the failing value instead of the whole color.
+fn expect<'a, O, F>(
+    expectation: Expectation,
+    mut parser: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: Parser<&'a str, O, SyntaxError<&'a str>>,
+{
+    move |input: &'a str| {
+        parser.parse(input).map_err(|error| {
+            error.map(|mut error| {
+                error.expected.get_or_insert_with(|| expectation.clone());
+                error
+            })
+        })
+    }
+}
+
+/// The values, that have been clamped while parsing an input.
+///
+/// The parsers of colors with clamped values take it as context and add the position of each clamped value.
+#[derive(Debug, Default)]
+pub struct Clamps(RefCell<Vec<(usize, usize)>>);
+
+impl Clamps {
+    /// Remembers a clamped value by the length of the remaining input before and after it,
+    /// which does not change as the parsers advance through the input.
+    fn push(&self, remaining_before: usize, remaining_after: usize) {
+        self.0
+            .borrow_mut()
+            .push((remaining_before, remaining_after));
+    }
+
+    /// Returns the byte ranges of the clamped values in the parsed input, in the order of the input.
+    pub fn spans(&self, input: &str) -> Vec<Range<usize>> {
+        let mut spans = self
+            .0
+            .borrow()
+            .iter()
+            .map(|&(before, after)| input.len() - before..input.len() - after)
+            .collect::<Vec<_>>();
+        // alternatives can parse the same value more than once
+        spans.sort_by_key(|span| span.start);
+        spans.dedup();
+        spans
+    }
+}
+
+/// Clamps the value of the parser to the given range.
+///
+/// Values outside of the range are added to the clamps, so they can be reported.
+fn clamped<'a, F>(
+    min: f32,
+    max: f32,
+    clamps: &'a Clamps,
+    mut parser: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, f32> + 'a
+where
+    F: Parser<&'a str, f32, SyntaxError<&'a str>> + 'a,
+{
+    move |input: &'a str| {
+        let (remaining, value) = parser.parse(input)?;
+        if !(min..=max).contains(&value) {
+            clamps.push(input.len(), remaining.len());
+        }
+        Ok((remaining, value.clamp(min, max)))
+    }
+}
 
 /// Parses a hexadecimal value from a string input and returns the parsed value.
 ///
@@ -23,25 +163,26 @@ use super::{cmyk::Cmyka, color::Color, hunterlab::HunterLab, position::AlphaPosi
 /// assert_eq!(result, Ok(("", 255)));
 /// ```
 fn hex(input: &str) -> IResult<&str, u8> {
-    map_res(
-        take_while_m_n(2, 2, |char| is_hex_digit(char as u8)),
-        |str| u8::from_str_radix(str, 16),
+    expect(
+        Expectation::HexDigits,
+        map_res(
+            take_while_m_n(2, 2, |char| is_hex_digit(char as u8)),
+            |str| u8::from_str_radix(str, 16),
+        ),
     )(input)
 }
-/// Parses a relative percentage displayed as a decimal, such as `0.5`.
-/// The leading digits before the decimal dot are optional.
-/// The result is clamped between 0.0 and 1.0.
-///
-/// # Examples
-///
-/// ```rust
-/// let result = relative_percentage("0.5");
-/// assert_eq!(result, Ok(("", 0.5)));
-/// ```
-fn relative_percentage(input: &str) -> IResult<&str, f32> {
-    let (input, digits) = recognize(separated_pair(digit0, tag("."), digit1))(input)?;
-    let (_, value) = nom::number::complete::float(digits)?;
-    Ok((input, value.clamp(0.0, 1.0)))
+/// Parses an alpha value, either as a percentage or as a number between 0 and 1, such as `50%` or `0.5`.
+/// The result is clamped between 0 and 1.
+fn alpha_value<'a>(clamps: &'a Clamps) -> impl FnMut(&'a str) -> IResult<&'a str, f32> + 'a {
+    expect(
+        Expectation::AlphaValue,
+        clamped(
+            0.0,
+            1.0,
+            clamps,
+            alt((parse_percentage, nom::number::complete::float)),
+        ),
+    )
 }
 
 /// Parses a percentage displayed as a number following a `%`.
@@ -49,13 +190,18 @@ fn relative_percentage(input: &str) -> IResult<&str, f32> {
 ///
 /// # Examples
 /// ```rust
-/// let result = percentage("50%");
+/// let result = percentage(&Clamps::default())("50%");
 /// assert_eq!(result, Ok(("", 0.5)));
 /// ```
-fn percentage(input: &str) -> IResult<&str, f32> {
-    let (input, digits) = terminated(digit1, tag("%"))(input)?;
-    let (_input, value) = nom::number::complete::float(digits)?;
-    Ok((input, (value / 100f32).clamp(0.0, 1.0)))
+fn percentage<'a>(clamps: &'a Clamps) -> impl FnMut(&'a str) -> IResult<&'a str, f32> + 'a {
+    expect(
+        Expectation::Percentage,
+        clamped(0.0, 1.0, clamps, |input| {
+            let (input, digits) = terminated(digit1, tag("%"))(input)?;
+            let (_input, value) = nom::number::complete::float(digits)?;
+            Ok((input, value / 100f32))
+        }),
+    )
 }
 
 /// Parses a percentage value, such as `-51.6%`.
@@ -115,16 +261,85 @@ fn separator(input: &str) -> IResult<&str, &str> {
 /// assert_eq!(result, Ok(("", 90.0)));
 ///```
 fn hue(input: &str) -> IResult<&str, f32> {
-    alt((
-        map(
-            terminated(nom::number::complete::float, tag("turn")),
-            |deg| deg * 360.0,
+    expect(
+        Expectation::Hue,
+        alt((
+            map(
+                terminated(nom::number::complete::float, tag("turn")),
+                |deg| deg * 360.0,
+            ),
+            terminated(
+                nom::number::complete::float,
+                opt(alt((tag("deg"), tag("°")))),
+            ),
+        )),
+    )(input)
+}
+
+/// Parses the `none` keyword of modern CSS, which stands for a missing component and is treated as zero.
+fn none(input: &str) -> IResult<&str, f32> {
+    value(0.0, tag_no_case("none"))(input)
+}
+
+/// Parses a percentage as used by the CSS color functions, such as `50%`, `50.5%` or `none`.
+///
+/// Modern CSS also allows plain numbers between 0 and 100, such as `50`.
+/// The result is clamped between 0 and 1.
+fn css_percentage<'a>(clamps: &'a Clamps) -> impl FnMut(&'a str) -> IResult<&'a str, f32> + 'a {
+    expect(
+        Expectation::CssPercentage,
+        clamped(
+            0.0,
+            1.0,
+            clamps,
+            alt((
+                parse_percentage,
+                none,
+                map(nom::number::complete::float, |value| value / 100.0),
+            )),
         ),
-        terminated(
-            nom::number::complete::float,
-            opt(alt((tag("deg"), tag("°")))),
+    )
+}
+
+/// Parses a red, green or blue value, such as `46`, `127.5`, `20%` or `none`.
+///
+/// If `fractions` is set, numbers with a decimal point up to `1.0` are treated as relative values,
+/// so `0.5` is half of `255`. Otherwise all numbers are between 0 and 255, as in modern CSS.
+/// The result is clamped between 0 and 1.
+fn rgb_component<'a>(
+    fractions: bool,
+    clamps: &'a Clamps,
+) -> impl FnMut(&'a str) -> IResult<&'a str, f32> + 'a {
+    expect(
+        Expectation::RgbValue,
+        clamped(
+            0.0,
+            1.0,
+            clamps,
+            alt((
+                parse_percentage,
+                none,
+                map_res(recognize_float, move |digits: &str| {
+                    digits.parse::<f32>().map(|value| {
+                        if fractions && digits.contains('.') && value <= 1.0 {
+                            value
+                        } else {
+                            value / 255.0
+                        }
+                    })
+                }),
+            )),
         ),
-    ))(input)
+    )
+}
+
+/// Whether the first value of a color function is followed by a comma or `|`,
+/// as in the legacy syntax of CSS, instead of a space.
+fn is_comma_separated(input: &str) -> bool {
+    let after_first_value = input
+        .trim_start()
+        .trim_start_matches(|char: char| !(char == ',' || char == '|' || char.is_whitespace()));
+    after_first_value.trim_start().starts_with([',', '|'])
 }
 
 /// Removes whitespace around the given parser, returning the result of the parser.
@@ -153,8 +368,21 @@ where
     delimited(opt(multispace0), inner, opt(multispace0))
 }
 
+/// Asserts that the parser succeeded and that its result matches the expected color,
+/// once both are rounded to 8-bit values.
+#[cfg(test)]
+fn assert_rgba8(expected: Color, result: IResult<&str, Color>) {
+    let (input, color) = result.expect("Failed to parse color");
+    assert_eq!("", input);
+    assert_eq!(expected.rgba8(), color.rgba8());
+}
+
 pub fn hex_color(input: &str, alpha_position: AlphaPosition) -> IResult<&str, Color> {
-    let (input, _) = opt(whitespace(tag("#")))(input)?;
+    let (input, _) = opt(whitespace(alt((tag("#"), tag_no_case("0x")))))(input)?;
+
+    if let Ok(result) = short_hex_color(input, alpha_position) {
+        return Ok(result);
+    }
 
     let (input, first_alpha) = if alpha_position == AlphaPosition::Start && input.len() >= 8 {
         hex(input)?
@@ -165,10 +393,10 @@ pub fn hex_color(input: &str, alpha_position: AlphaPosition) -> IResult<&str, Co
     let (input, (red, green, blue)) =
         (whitespace(hex), whitespace(hex), whitespace(hex)).parse(input)?;
 
-    let alpha = match alpha_position {
-        AlphaPosition::None => 255,
-        AlphaPosition::Start => first_alpha,
-        AlphaPosition::End => opt(hex)(input)?.1.unwrap_or(255),
+    let (input, alpha) = match alpha_position {
+        AlphaPosition::None => (input, 255),
+        AlphaPosition::Start => (input, first_alpha),
+        AlphaPosition::End => map(opt(whitespace(hex)), |alpha| alpha.unwrap_or(255))(input)?,
     };
 
     let color = Color::rgba(red, green, blue, alpha);
@@ -176,62 +404,125 @@ pub fn hex_color(input: &str, alpha_position: AlphaPosition) -> IResult<&str, Co
     Ok((input, color))
 }
 
-// #[cfg(test)]
-// mod parse_hex {
-//     use super::*;
-
-//     #[test]
-//     fn it_parse_hex_without_alpha() {
-//         assert_eq!(
-//             Color::rgb(46, 52, 64),
-//             hex_color("2e3440", AlphaPosition::None).unwrap().1
-//         );
-//         assert_eq!(
-//             Color::rgb(46, 52, 64),
-//             hex_color("#2e3440", AlphaPosition::None).unwrap().1
-//         );
-//     }
-
-//     #[test]
-//     fn it_parse_hex_with_alpha_start() {
-//         assert_eq!(
-//             Color::rgba(46, 52, 64, 40),
-//             hex_color("282e3440", AlphaPosition::Start).unwrap().1
-//         );
-//         assert_eq!(
-//             Color::rgb(46, 52, 64),
-//             hex_color("#2e3440", AlphaPosition::None).unwrap().1
-//         );
-//     }
-
-//     #[test]
-//     fn it_parse_hex_with_alpha_end() {
-//         assert_eq!(
-//             Color::rgba(46, 52, 64, 40),
-//             hex_color("2e344028", AlphaPosition::End).unwrap().1
-//         );
-//         assert_eq!(
-//             Color::rgba(46, 52, 64, 40),
-//             hex_color("#2e344028", AlphaPosition::End).unwrap().1
-//         );
-//     }
-
-//     #[test]
-//     fn success_with_whitespace() {
-//         assert_eq!(
-//             Color::rgba(46, 52, 64, 40),
-//             hex_color("     #2e344028", AlphaPosition::End).unwrap().1
-//         );
-//         assert_eq!(
-//             Color::rgba(46, 52, 64, 40),
-//             hex_color(" # 2e 34 40 28", AlphaPosition::End).unwrap().1
-//         );
-//         assert_eq!(
-//             Color::rgba(46, 52, 64, 40),
-//             hex_color("2e 34 40 28", AlphaPosition::End).unwrap().1
-//         );
-//     }
-// }
+/// Parses a hex color with a single digit per component, such as `fff` or `f0a8`,
+/// where each digit is repeated, so `f0a` is the same as `ff00aa`.
+///
+/// The fourth digit is the alpha value at the given position, and is ignored without an alpha value.
+fn short_hex_color(input: &str, alpha_position: AlphaPosition) -> IResult<&str, Color> {
+    let (input, digits) = terminated(
+        take_while_m_n(3, 4, |char: char| char.is_ascii_hexdigit()),
+        not(satisfy(|char| char.is_ascii_hexdigit())),
+    )(input)?;
+    let values = digits
+        .chars()
+        .filter_map(|digit| digit.to_digit(16))
+        .map(|value| value as u8 * 17)
+        .collect::<Vec<_>>();
+
+    let color = match (values.as_slice(), alpha_position) {
+        ([alpha, red, green, blue], AlphaPosition::Start) => {
+            Color::rgba(*red, *green, *blue, *alpha)
+        }
+        ([red, green, blue, alpha], AlphaPosition::End) => Color::rgba(*red, *green, *blue, *alpha),
+        ([red, green, blue, ..], _) => Color::rgb(*red, *green, *blue),
+        _ => unreachable!("Expected three or four digits"),
+    };
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_hex {
+    use super::*;
+
+    #[test]
+    fn it_parses_short_hex() {
+        assert_rgba8(
+            Color::rgb(255, 255, 255),
+            hex_color("#fff", AlphaPosition::None),
+        );
+        assert_rgba8(
+            Color::rgb(255, 0, 170),
+            hex_color("f0a", AlphaPosition::End),
+        );
+        assert_rgba8(
+            Color::rgba(255, 0, 170, 136),
+            hex_color("#f0a8", AlphaPosition::End),
+        );
+        assert_rgba8(
+            Color::rgba(255, 0, 170, 136),
+            hex_color("#8f0a", AlphaPosition::Start),
+        );
+        assert_rgba8(
+            Color::rgb(255, 0, 170),
+            hex_color("#f0a8", AlphaPosition::None),
+        );
+    }
+
+    #[test]
+    fn it_parses_the_0x_prefix() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            hex_color("0x2e3440", AlphaPosition::None),
+        );
+        assert_rgba8(
+            Color::rgb(255, 255, 255),
+            hex_color("0xFFF", AlphaPosition::None),
+        );
+    }
+
+    #[test]
+    fn it_parse_hex_without_alpha() {
+        assert_eq!(
+            Color::rgb(46, 52, 64),
+            hex_color("2e3440", AlphaPosition::None).unwrap().1
+        );
+        assert_eq!(
+            Color::rgb(46, 52, 64),
+            hex_color("#2e3440", AlphaPosition::None).unwrap().1
+        );
+    }
+
+    #[test]
+    fn it_parse_hex_with_alpha_start() {
+        assert_eq!(
+            Color::rgba(46, 52, 64, 40),
+            hex_color("282e3440", AlphaPosition::Start).unwrap().1
+        );
+        assert_eq!(
+            Color::rgb(46, 52, 64),
+            hex_color("#2e3440", AlphaPosition::None).unwrap().1
+        );
+    }
+
+    #[test]
+    fn it_parse_hex_with_alpha_end() {
+        assert_eq!(
+            Color::rgba(46, 52, 64, 40),
+            hex_color("2e344028", AlphaPosition::End).unwrap().1
+        );
+        assert_eq!(
+            Color::rgba(46, 52, 64, 40),
+            hex_color("#2e344028", AlphaPosition::End).unwrap().1
+        );
+    }
+
+    #[test]
+    fn success_with_whitespace() {
+        assert_eq!(
+            Color::rgba(46, 52, 64, 40),
+            hex_color("     #2e344028", AlphaPosition::End).unwrap().1
+        );
+        assert_eq!(
+            Color::rgba(46, 52, 64, 40),
+            hex_color(" # 2e 34 40 28", AlphaPosition::End).unwrap().1
+        );
+        assert_eq!(
+            Color::rgba(46, 52, 64, 40),
+            hex_color("2e 34 40 28", AlphaPosition::End).unwrap().1
+        );
+    }
+}
 
 /// Parses a rgb representation of a color.
 ///
@@ -244,40 +535,57 @@ pub fn hex_color(input: &str, alpha_position: AlphaPosition) -> IResult<&str, Co
 /// - a float with an optional decimal point or percentage sign
 ///
 /// Mixed value types are allowed.
-pub fn rgb(input: &str) -> IResult<&str, Color> {
+///
+/// Both the legacy syntax, such as `rgba(46, 52, 64, 0.5)`, and the modern syntax of CSS,
+/// such as `rgb(46 52 64 / 50%)`, are accepted. In the modern syntax, the alpha value
+/// follows a `/` and is a number between 0 and 1 or a percentage.
+pub fn rgb<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
     let (input, alpha) = whitespace(alt((
         value(AlphaPosition::None, tag("rgb(")),
         value(AlphaPosition::End, tag("rgba(")),
         value(AlphaPosition::Start, tag("argb(")),
     )))(input)?;
 
-    let minimum_length = if alpha == AlphaPosition::None { 3 } else { 4 };
-
-    let (input, mut color_values) = many_m_n(
-        minimum_length,
-        4,
-        terminated(
-            whitespace(alt((
-                map(alt((percentage, relative_percentage)), |percent| {
-                    (percent * 255f32) as u8
-                }),
-                nom::character::complete::u8,
-            ))),
-            opt(whitespace(separator)),
-        ),
-    )(input)?;
+    let (input, color_values) = if alpha == AlphaPosition::Start {
+        let (input, mut color_values) = many_m_n(
+            4,
+            4,
+            terminated(
+                whitespace(rgb_component(true, clamps)),
+                opt(whitespace(separator)),
+            ),
+        )(input)?;
+        color_values.rotate_left(1);
+        (input, color_values)
+    } else {
+        // only the legacy syntax treats values such as `0.5` as fractions, CSS reads them as numbers up to 255
+        let legacy = is_comma_separated(input);
+        let (input, mut color_values) = many_m_n(
+            3,
+            3,
+            terminated(
+                whitespace(rgb_component(legacy, clamps)),
+                opt(whitespace(alt((tag(","), tag("|"))))),
+            ),
+        )(input)?;
+        let (input, alpha) = opt(alt((
+            preceded(
+                whitespace(tag("/")),
+                whitespace(alt((alpha_value(clamps), none))),
+            ),
+            whitespace(rgb_component(legacy, clamps)),
+        )))(input)?;
+        color_values.extend(alpha);
+        (input, color_values)
+    };
 
     let (input, _output) = opt(whitespace(tag(")")))(input)?;
 
-    if alpha == AlphaPosition::Start {
-        assert!(color_values.len() == 4);
-        color_values.swap(0, 3)
-    }
-    let color = Color::rgba(
+    let color = Color::new(
         color_values[0],
         color_values[1],
         color_values[2],
-        *color_values.get(3).unwrap_or(&255),
+        *color_values.get(3).unwrap_or(&1.0),
     );
 
     Ok((input, color))
@@ -291,54 +599,112 @@ mod parse_rgb {
     fn it_parses_basic() {
         assert_eq!(
             Ok(("", Color::rgba(46, 52, 64, 255))),
-            rgb("rgb(46, 52, 64)")
+            rgb("rgb(46, 52, 64)", &Clamps::default())
         );
         assert_eq!(
             Ok(("", Color::rgba(46, 52, 64, 100))),
-            rgb("rgba(46, 52, 64, 100)")
+            rgb("rgba(46, 52, 64, 100)", &Clamps::default())
         );
         assert_eq!(
             Ok(("", Color::rgba(46, 52, 64, 100))),
-            rgb("argb(100  46 | 52 / 64)")
+            rgb("argb(100  46 | 52 / 64)", &Clamps::default())
         );
     }
 
     #[test]
     fn it_parses_percent() {
+        // percentages are kept as is, instead of being rounded to 8-bit values
         assert_eq!(
-            Ok(("", Color::rgba(46, 51, 64, 255))),
-            rgb("rgb(46, 20%, 64)")
+            Ok(("", Color::new(46.0 / 255.0, 0.2, 64.0 / 255.0, 1.0))),
+            rgb("rgb(46, 20%, 64)", &Clamps::default())
         );
         assert_eq!(
-            Ok(("", Color::rgba(45, 51, 63, 255))),
-            rgb("rgba(18%, 20%, 25%, 100%)")
+            Ok(("", Color::new(0.18, 0.2, 0.25, 1.0))),
+            rgb("rgba(18%, 20%, 25%, 100%)", &Clamps::default())
         );
         assert_eq!(
-            Ok(("", Color::rgba(127, 127, 127, 255))),
-            rgb("rgb(0.5, 0.5, 0.5)")
+            Ok(("", Color::new(0.5, 0.5, 0.5, 1.0))),
+            rgb("rgb(0.5, 0.5, 0.5)", &Clamps::default())
         );
     }
+
+    #[test]
+    fn it_parses_modern_syntax() {
+        assert_rgba8(
+            Color::rgba(255, 0, 0, 128),
+            rgb("rgb(255 0 0 / 50%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(255, 0, 0, 128),
+            rgb("rgb(255 0 0 / 0.5)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(255, 0, 0),
+            rgb("rgb(255 0 0 / 1)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(128, 0, 0),
+            rgb("rgb(50.2% none 0)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(128, 0, 0),
+            rgb("rgba(127.5 0 0)", &Clamps::default()),
+        );
+    }
+
+    #[test]
+    fn it_reads_numbers_of_the_modern_syntax_up_to_255() {
+        assert_rgba8(Color::rgb(1, 0, 0), rgb("rgb(1.0 0 0)", &Clamps::default()));
+        assert_rgba8(Color::rgb(2, 0, 0), rgb("rgb(1.5 0 0)", &Clamps::default()));
+        assert_rgba8(
+            Color::rgba(0, 0, 128, 128),
+            rgb("rgb(0 0 127.5 / 0.5)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(255, 0, 0),
+            rgb("rgb(1.0, 0, 0)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(128, 0, 0),
+            rgb("rgb(0.5 | 0 | 0)", &Clamps::default()),
+        );
+    }
+
+    #[test]
+    fn it_reports_clamped_values() {
+        let clamps = Clamps::default();
+        let input = "rgba(300, 52, -64, 200%)";
+        assert_rgba8(Color::rgb(255, 52, 0), rgb(input, &clamps));
+        assert_eq!(vec![5..8, 14..17, 19..23], clamps.spans(input));
+
+        // the positions do not depend on where the input is stored
+        let copy = input.to_owned();
+        assert_eq!(vec![5..8, 14..17, 19..23], clamps.spans(&copy));
+    }
 }
 
 /// Parses a hsl representation of a color.
 ///
 ///
 /// Mixed value types are allowed.
-pub fn hsl(input: &str) -> IResult<&str, Color> {
+///
+/// Both the legacy syntax, such as `hsla(220, 16%, 22%, 0.5)`, and the modern syntax of CSS,
+/// such as `hsl(220deg 16% 22% / 50%)`, are accepted.
+pub fn hsl<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
     let (input, _) = whitespace(alt((tag("hsl("), tag("hsla("))))(input)?;
 
-    let (input, hue) = terminated(whitespace(hue), opt(whitespace(separator)))(input)?;
+    let (input, hue) = terminated(whitespace(alt((hue, none))), opt(whitespace(separator)))(input)?;
 
     let (input, color_values) = many_m_n(
         2,
         2,
-        terminated(whitespace(percentage), opt(whitespace(separator))),
+        terminated(
+            whitespace(css_percentage(clamps)),
+            opt(whitespace(separator)),
+        ),
     )(input)?;
 
-    let (input, alpha) = opt(map(
-        whitespace(alt((percentage, relative_percentage))),
-        |percent| percent,
-    ))(input)?;
+    let (input, alpha) = opt(whitespace(alt((alpha_value(clamps), none))))(input)?;
 
     let (input, _output) = opt(whitespace(tag(")")))(input)?;
 
@@ -358,20 +724,42 @@ mod parse_hsl {
 
     #[test]
     fn it_parses_basic() {
-        assert_eq!(Ok(("", Color::rgb(47, 53, 65))), hsl("hsl(220, 16%, 22%)"));
-        assert_eq!(
-            Ok(("", Color::rgba(47, 53, 65, 99))),
-            hsl("hsl(220, 16%, 22%, 39%)")
+        assert_rgba8(
+            Color::rgb(47, 53, 65),
+            hsl("hsl(220, 16%, 22%)", &Clamps::default()),
         );
-        assert_eq!(
-            Ok(("", Color::rgba(47, 53, 65, 127))),
-            hsl("hsla(220, 16%, 22%, 0.5)")
+        assert_rgba8(
+            Color::rgba(47, 53, 65, 99),
+            hsl("hsl(220, 16%, 22%, 39%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(47, 53, 65, 128),
+            hsl("hsla(220, 16%, 22%, 0.5)", &Clamps::default()),
         );
     }
 
     #[test]
     fn it_works_with_deg() {
-        assert_eq!(Ok(("", Color::rgb(47, 53, 65))), hsl("hsl(220, 16%, 22%)"));
+        assert_rgba8(
+            Color::rgb(47, 53, 65),
+            hsl("hsl(220, 16%, 22%)", &Clamps::default()),
+        );
+    }
+
+    #[test]
+    fn it_parses_modern_syntax() {
+        assert_rgba8(
+            Color::rgb(64, 191, 64),
+            hsl("hsl(120deg 50% 50%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(64, 191, 64, 128),
+            hsl("hsl(120deg 50 50 / 50%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(128, 128, 128),
+            hsl("hsl(none none 50.2%)", &Clamps::default()),
+        );
     }
 }
 
@@ -379,7 +767,7 @@ mod parse_hsl {
 ///
 ///
 /// Mixed value types are allowed.
-pub fn hsv(input: &str) -> IResult<&str, Color> {
+pub fn hsv<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
     let (input, _) = whitespace(alt((tag("hsv("), tag("hsva("))))(input)?;
 
     let (input, hue) = terminated(whitespace(hue), opt(whitespace(separator)))(input)?;
@@ -387,13 +775,10 @@ pub fn hsv(input: &str) -> IResult<&str, Color> {
     let (input, color_values) = many_m_n(
         2,
         2,
-        terminated(whitespace(percentage), opt(whitespace(separator))),
+        terminated(whitespace(percentage(clamps)), opt(whitespace(separator))),
     )(input)?;
 
-    let (input, alpha) = opt(map(
-        whitespace(alt((percentage, relative_percentage))),
-        |percent| percent,
-    ))(input)?;
+    let (input, alpha) = opt(map(whitespace(alpha_value(clamps)), |percent| percent))(input)?;
 
     let (input, _output) = opt(whitespace(tag(")")))(input)?;
 
@@ -408,26 +793,29 @@ mod parse_hsv {
 
     #[test]
     fn it_parses() {
-        assert_eq!(Ok(("", Color::rgb(46, 52, 64))), hsv("hsv(220, 28%, 25%)"));
-        assert_eq!(
-            Ok(("", Color::rgba(46, 52, 64, 127))),
-            hsv("hsv(220, 28%, 25%, 50%)")
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            hsv("hsv(220, 28%, 25%)", &Clamps::default()),
         );
-        assert_eq!(
-            Ok(("", Color::rgba(46, 52, 64, 127))),
-            hsv("hsva(220, 28%, 25%, 0.5)")
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            hsv("hsv(220, 28%, 25%, 50%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            hsv("hsva(220, 28%, 25%, 0.5)", &Clamps::default()),
         );
     }
 }
 
 /// Parses a cmyk representation of a color.
-pub fn cmyk(input: &str) -> IResult<&str, Color> {
+pub fn cmyk<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
     let (input, color_values) = delimited(
         whitespace(tag("cmyk(")),
         many_m_n(
             4,
             4,
-            terminated(whitespace(percentage), opt(whitespace(separator))),
+            terminated(whitespace(percentage(clamps)), opt(whitespace(separator))),
         ),
         opt(whitespace(tag(")"))),
     )(input)?;
@@ -449,15 +837,17 @@ mod parse_cmyk {
 
     #[test]
     fn it_parses() {
-        assert_eq!(
-            Ok(("", Color::rgb(46, 52, 64))),
-            cmyk("cmyk(28%, 19%, 0%, 75%)")
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            cmyk("cmyk(28%, 19%, 0%, 75%)", &Clamps::default()),
         );
     }
 }
 
 /// Parses a xyz representation of a color.
-pub fn xyz(input: &str) -> IResult<&str, Color> {
+///
+/// The values are expected to be scaled, so that the reference white has a luminance of 100.
+pub fn xyz(input: &str, reference_white: ReferenceWhite) -> IResult<&str, Color> {
     let (input, color_values) = delimited(
         whitespace(tag_no_case("XYZ(")),
         many_m_n(
@@ -471,9 +861,17 @@ pub fn xyz(input: &str) -> IResult<&str, Color> {
         opt(whitespace(tag(")"))),
     )(input)?;
 
-    let color = palette::Xyz::new(color_values[0], color_values[1], color_values[2]);
+    let color = Color::from_xyz(
+        palette::Xyza::new(
+            color_values[0] / 100.0,
+            color_values[1] / 100.0,
+            color_values[2] / 100.0,
+            1.0,
+        ),
+        reference_white,
+    );
 
-    Ok((input, Color::from_palette(color)))
+    Ok((input, color))
 }
 
 #[cfg(test)]
@@ -482,90 +880,211 @@ mod parse_xyz {
 
     #[test]
     fn it_parses() {
-        assert_eq!(
-            Ok(("", Color::rgb(46, 52, 64))),
-            xyz("XYZ(3.280, 3.407, 5.335)")
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            xyz("XYZ(3.280, 3.407, 5.335)", ReferenceWhite::default()),
         );
     }
 }
 
-/// Parses a cielab representation of a color.
-pub fn cielab(input: &str) -> IResult<&str, Color> {
-    let (input, _) = whitespace(alt((tag_no_case("lab("), tag_no_case("cielab("))))(input)?;
+/// Parses a CIE xyY representation of a color.
+///
+/// The luminance is expected between 0 and 100, like the values of XYZ.
+pub fn xyy(input: &str, reference_white: ReferenceWhite) -> IResult<&str, Color> {
+    let (input, color_values) = delimited(
+        whitespace(tag_no_case("xyY(")),
+        many_m_n(
+            3,
+            3,
+            terminated(
+                whitespace(nom::number::complete::float),
+                opt(whitespace(separator)),
+            ),
+        ),
+        opt(whitespace(tag(")"))),
+    )(input)?;
+
+    let color = Color::from_xyy(
+        palette::Yxya::new(
+            color_values[0],
+            color_values[1],
+            color_values[2] / 100.0,
+            1.0,
+        ),
+        reference_white,
+    );
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_xyy {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            xyy("xyY(0.2727, 0.2833, 3.407)", ReferenceWhite::default()),
+        );
+    }
+
+    #[test]
+    fn it_parses_black() {
+        assert_rgba8(
+            Color::rgb(0, 0, 0),
+            xyy("xyy(0.3127 0 0)", ReferenceWhite::default()),
+        );
+    }
+}
 
+/// Parses the values of a CIELAB color, following the opening parenthesis.
+fn lab_values<'a>(
+    input: &'a str,
+    reference_white: ReferenceWhite,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
     //can either be an percentage or a number between 0 and 100
     let (input, cie_l) = terminated(
-        whitespace(alt((
-            map(whitespace(parse_percentage), |percentage| {
-                percentage * 100.0
-            }),
-            nom::number::complete::float,
-        ))),
+        whitespace(clamped(
+            0.0,
+            100.0,
+            clamps,
+            alt((
+                map(parse_percentage, |percentage| percentage * 100.0),
+                nom::number::complete::float,
+            )),
+        )),
         opt(whitespace(separator)),
     )(input)?;
 
-    //both CIE a and CIE b can either be an percentage between -100% and 100% or a number between -125 and 125
+    //both CIE a and CIE b can either be an percentage between -100% and 100%, which maps to -125 and 125, or an unbounded number
     let (input, cie_a_b) = many_m_n(
         2,
         2,
         terminated(
             whitespace(alt((
-                map(alt((parse_percentage, percentage)), |percentage| {
-                    percentage * 125.0
-                }),
+                map(parse_percentage, |percentage| percentage * 125.0),
                 nom::number::complete::float,
             ))),
             opt(whitespace(separator)),
         ),
     )(input)?;
 
-    let (input, alpha) = opt(whitespace(map(
-        alt((percentage, relative_percentage)),
-        |percentage| percentage,
-    )))(input)?;
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
 
     let (input, _) = opt(whitespace(tag(")")))(input)?;
 
-    let color = Color::from_palette(palette::Laba::new(
-        cie_l.clamp(0.0, 100.0),
-        cie_a_b[0].clamp(-125.0, 125.0),
-        cie_a_b[1].clamp(-125.0, 125.0),
-        alpha.unwrap_or(1.0),
-    ));
+    let color = Color::from_lab(
+        palette::Laba::new(cie_l, cie_a_b[0], cie_a_b[1], alpha.unwrap_or(1.0)),
+        reference_white,
+    );
 
     Ok((input, color))
 }
 
+/// Parses a colorimetric CIELAB representation of a color, relative to the given reference white.
+pub fn cielab<'a>(
+    input: &'a str,
+    reference_white: ReferenceWhite,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("cielab("))(input)?;
+    lab_values(input, reference_white, clamps)
+}
+
 #[cfg(test)]
 mod parse_cie_lab {
     use super::*;
 
     #[test]
     fn it_parses() {
-        assert_eq!(
-            Ok(("", Color::rgb(46, 52, 64))),
-            cielab(" lab(21.61%, 0.56%,  -6.68%)")
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            cielab(
+                " cielab(21.61%, 0.56%,  -6.68%)",
+                ReferenceWhite::default(),
+                &Clamps::default(),
+            ),
         );
-        assert_eq!(
-            Ok(("", Color::rgb(46, 52, 64))),
-            cielab("lab(21.61, 0.70, -8.35)")
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            cielab(
+                "cielab(21.61, 0.70, -8.35)",
+                ReferenceWhite::default(),
+                &Clamps::default(),
+            ),
         );
     }
+
+    #[test]
+    fn it_rejects_css_lab() {
+        assert!(cielab(
+            "lab(21.61, 0.70, -8.35)",
+            ReferenceWhite::default(),
+            &Clamps::default()
+        )
+        .is_err());
+    }
 }
 
-/// Parses a hwb representation of a color.
-pub fn hwb(input: &str) -> IResult<&str, Color> {
-    let (input, _) = tag("hwb(")(input)?;
+/// Parses a CSS `lab()` representation of a color.
+///
+/// Following the CSS Color Module Level 4, the values are relative to D50.
+pub fn lab<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("lab("))(input)?;
+    lab_values(input, ReferenceWhite::d50(), clamps)
+}
 
-    let (input, hue) = terminated(whitespace(hue), opt(whitespace(separator)))(input)?;
+#[cfg(test)]
+mod parse_lab {
+    use super::*;
 
-    let (input, color_values) = many_m_n(
-        2,
-        2,
-        terminated(whitespace(percentage), opt(whitespace(separator))),
-    )(input)?;
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(255, 0, 0),
+            lab("lab(54.29% 80.81 69.89)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            lab("lab(21.51 -0.2 -8.46)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            lab("lab(21.51 -0.2 -8.46 / 0.5)", &Clamps::default()),
+        );
+    }
 
-    let (input, alpha) = opt(whitespace(alt((percentage, relative_percentage))))(input)?;
+    #[test]
+    fn it_reports_clamped_values() {
+        let clamps = Clamps::default();
+        let input = "lab(150 -0.2 -8.46 / 1.5)";
+        let (_, color) = lab(input, &clamps).unwrap();
+        assert_eq!(1.0, color.alpha);
+        assert_eq!(vec![4..7, 21..24], clamps.spans(input));
+    }
+}
+
+/// Parses a hwb representation of a color.
+///
+/// Both the comma separated syntax, such as `hwb(220, 18%, 75%)`, and the modern syntax of CSS,
+/// such as `hwb(220 18% 75% / 0.5)`, are accepted.
+pub fn hwb<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag("hwb("))(input)?;
+
+    let (input, hue) = terminated(whitespace(alt((hue, none))), opt(whitespace(separator)))(input)?;
+
+    let (input, color_values) = many_m_n(
+        2,
+        2,
+        terminated(
+            whitespace(css_percentage(clamps)),
+            opt(whitespace(separator)),
+        ),
+    )(input)?;
+
+    let (input, alpha) = opt(whitespace(alt((alpha_value(clamps), none))))(input)?;
 
     let (input, _output) = opt(whitespace(tag(")")))(input)?;
 
@@ -585,69 +1104,118 @@ mod parse_hwb {
 
     #[test]
     fn it_parses() {
-        assert_eq!(Ok(("", Color::rgb(46, 52, 64))), hwb("hwb(220, 18%, 75%)"));
-        assert_eq!(
-            Ok(("", Color::rgba(46, 52, 64, 127))),
-            hwb("hwb(220, 18%, 75%, 0.5)")
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            hwb("hwb(220, 18%, 75%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            hwb("hwb(220, 18%, 75%, 0.5)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            hwb(" hwb(220deg 18% 75% / 50%)", &Clamps::default()),
         );
     }
 }
 
-/// Parses a lch representation of a color.
-pub fn lch(input: &str) -> IResult<&str, Color> {
-    let (input, _) = tag("lch(")(input)?;
-
+/// Parses the values of a CIELCh color, following the opening parenthesis.
+fn lch_values<'a>(
+    input: &'a str,
+    reference_white: ReferenceWhite,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
     let (input, lightness) = terminated(
-        alt((
-            map(percentage, |percent| percent * 100.0),
+        whitespace(alt((
+            map(parse_percentage, |percent| percent * 100.0),
             nom::number::complete::float,
-        )),
+        ))),
         opt(whitespace(separator)),
     )(input)?;
 
     let (input, chroma) = terminated(
-        alt((
-            map(percentage, |percent| percent * 150.0),
+        whitespace(alt((
+            map(parse_percentage, |percent| percent * 150.0),
             nom::number::complete::float,
-        )),
+        ))),
         opt(whitespace(separator)),
     )(input)?;
 
-    let (input, hue) = terminated(hue, opt(whitespace(separator)))(input)?;
+    let (input, hue) = terminated(whitespace(hue), opt(whitespace(separator)))(input)?;
 
-    let (input, alpha) = opt(whitespace(alt((percentage, relative_percentage))))(input)?;
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
 
     let (input, _) = opt(whitespace(tag(")")))(input)?;
 
-    let color = Color::from_palette(palette::Lcha::new(
-        lightness,
-        chroma,
-        hue,
-        alpha.unwrap_or(1.0),
-    ));
+    let lab: palette::Laba<palette::white_point::Any> =
+        palette::Lcha::new(lightness, chroma, hue, alpha.unwrap_or(1.0)).into_color_unclamped();
+    let color = Color::from_lab(lab, reference_white);
 
     Ok((input, color))
 }
 
+/// Parses a colorimetric CIELCh representation of a color, relative to the given reference white.
+pub fn cielch<'a>(
+    input: &'a str,
+    reference_white: ReferenceWhite,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("cielch("))(input)?;
+    lch_values(input, reference_white, clamps)
+}
+
+#[cfg(test)]
+mod parse_cie_lch {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            cielch(
+                "cielch(21.605232, 8.378235, 274.76328)",
+                ReferenceWhite::default(),
+                &Clamps::default(),
+            ),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            cielch(
+                "cielch(21.605232, 8.378235, 274.76328, 0.5)",
+                ReferenceWhite::default(),
+                &Clamps::default(),
+            ),
+        );
+    }
+}
+
+/// Parses a CSS `lch()` representation of a color.
+///
+/// Following the CSS Color Module Level 4, the values are relative to D50.
+pub fn lch<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("lch("))(input)?;
+    lch_values(input, ReferenceWhite::d50(), clamps)
+}
+
 #[cfg(test)]
 mod parse_lch {
     use super::*;
 
     #[test]
     fn it_parses_lch() {
-        assert_eq!(
-            Ok(("", Color::rgb(46, 52, 64))),
-            lch("lch(21.605232, 8.378235, 274.76328)")
+        assert_rgba8(
+            Color::rgb(255, 0, 0),
+            lch("lch(54.29% 106.84 40.85)", &Clamps::default()),
         );
-        assert_eq!(
-            Ok(("", Color::rgba(46, 52, 64, 127))),
-            lch("lch(21.605232, 8.378235, 274.76328, 0.5)")
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            lch("lch(21.51 8.46 268.63 / 0.5)", &Clamps::default()),
         );
     }
 }
 
 /// Parses a LMS representation of a color.
-pub fn lms(input: &str) -> IResult<&str, Color> {
+pub fn lms(input: &str, reference_white: ReferenceWhite) -> IResult<&str, Color> {
     let (input, long) = delimited(
         whitespace(tag("L:")),
         whitespace(nom::number::complete::float),
@@ -664,7 +1232,7 @@ pub fn lms(input: &str) -> IResult<&str, Color> {
         opt(whitespace(separator)),
     )(input)?;
 
-    let color = Color::from_lms(long, medium, short, 255);
+    let color = Color::from_lms(long, medium, short, 1.0, reference_white);
 
     Ok((input, color))
 }
@@ -675,15 +1243,18 @@ mod parse_lms {
 
     #[test]
     fn it_parses() {
-        assert_eq!(
-            Ok(("", Color::rgb(46, 52, 64))),
-            lms("L: 3.20580, M: 3.52562, S: 5.33522")
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            lms(
+                "L: 0.032057, M: 0.035255, S: 0.053343",
+                ReferenceWhite::default(),
+            ),
         );
     }
 }
 
 /// Parses a hunter lab representation of a color.
-pub fn hunter_lab(input: &str) -> IResult<&str, Color> {
+pub fn hunter_lab(input: &str, reference_white: ReferenceWhite) -> IResult<&str, Color> {
     let (input, l) = delimited(
         whitespace(tag("L:")),
         whitespace(nom::number::complete::float),
@@ -700,9 +1271,10 @@ pub fn hunter_lab(input: &str) -> IResult<&str, Color> {
         opt(whitespace(separator)),
     )(input)?;
 
-    let color = HunterLab::new(l, a, b);
+    let xyz = HunterLab::<palette::white_point::Any>::new(l, a, b).into_xyz(reference_white.xyz());
+    let color = Color::from_xyz(xyz.with_alpha(1.0), reference_white);
 
-    Ok((input, Color::from_palette(color)))
+    Ok((input, color))
 }
 
 #[cfg(test)]
@@ -711,14 +1283,17 @@ mod tests {
 
     #[test]
     fn parse_hunter_lab() {
-        assert_eq!(
-            Ok(("", Color::rgb(46, 52, 64))),
-            hunter_lab("L: 18.45804, a: 0.41141, b: -5.42239",)
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            hunter_lab(
+                "L: 18.45804, a: 0.41141, b: -5.42239",
+                ReferenceWhite::default(),
+            ),
         );
     }
 }
 
-pub fn oklab(input: &str) -> IResult<&str, Color> {
+pub fn oklab<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
     let (input, _) = tag("oklab(")(input)?;
 
     //lightness can either be a percentage or a number between 0 and 1
@@ -736,7 +1311,7 @@ pub fn oklab(input: &str) -> IResult<&str, Color> {
         2,
         terminated(
             whitespace(alt((
-                map(alt((parse_percentage, percentage)), |percentage| {
+                map(alt((parse_percentage, percentage(clamps))), |percentage| {
                     percentage * 0.4
                 }),
                 nom::number::complete::float,
@@ -745,11 +1320,11 @@ pub fn oklab(input: &str) -> IResult<&str, Color> {
         ),
     )(input)?;
 
-    let (input, alpha) = opt(whitespace(alt((percentage, relative_percentage))))(input)?;
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
 
     let (input, _) = opt(whitespace(tag(")")))(input)?;
 
-    let color = Color::from_palette(palette::Oklaba::new(
+    let color = Color::from_palette_unclamped(palette::Oklaba::new(
         lightness,
         ok_a_b[0],
         ok_a_b[1],
@@ -765,14 +1340,14 @@ mod parse_oklab {
 
     #[test]
     fn parses_oklab() {
-        assert_eq!(
-            Ok(("", Color::rgb(46, 52, 64))),
-            oklab("32% -0.003600 -0.023222")
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            oklab("oklab(32.44% -0.002326 -0.022826)", &Clamps::default()),
         );
     }
 }
 
-pub fn oklch(input: &str) -> IResult<&str, Color> {
+pub fn oklch<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
     let (input, _) = tag("oklch(")(input)?;
 
     //lightness can either be a percentage or a number between 0 and 1
@@ -804,11 +1379,11 @@ pub fn oklch(input: &str) -> IResult<&str, Color> {
         opt(whitespace(separator)),
     )(input)?;
 
-    let (input, alpha) = opt(whitespace(alt((percentage, relative_percentage))))(input)?;
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
 
     let (input, _) = opt(whitespace(tag(")")))(input)?;
 
-    let color = Color::from_palette(palette::Oklcha::new(
+    let color = Color::from_palette_unclamped(palette::Oklcha::new(
         lightness,
         chroma,
         hue,
@@ -824,9 +1399,1440 @@ mod parse_oklch {
 
     #[test]
     fn parses_oklch() {
-        assert_eq!(
-            Ok(("", Color::rgb(46, 52, 64))),
-            oklch("32% 0.023499 261.187836")
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            oklch("oklch(32.44% 0.022945 264.182)", &Clamps::default()),
+        );
+    }
+}
+
+/// Parses a color in one of the wide gamut [`RgbSpace`]s, using the CSS `color()` syntax,
+/// such as `color(display-p3 0.92 0.2 0.14 / 0.5)`.
+///
+/// The function name and color space identifier are optional, so plain values are accepted as well.
+/// Each component can either be a number or a percentage. Values outside of `0.0..=1.0` are kept,
+/// as they represent colors outside of the gamut of the color space.
+pub fn rgb_space<'a>(
+    input: &'a str,
+    space: RgbSpace,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = opt(whitespace(tag_no_case("color(")))(input)?;
+    let (input, _) = opt(whitespace(tag_no_case(space.css_name())))(input)?;
+
+    let (input, components) = many_m_n(
+        3,
+        3,
+        terminated(
+            whitespace(alt((parse_percentage, nom::number::complete::float))),
+            opt(whitespace(separator)),
+        ),
+    )(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let color = space.color(
+        [components[0], components[1], components[2]],
+        alpha.unwrap_or(1.0),
+    );
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_rgb_space {
+    use super::*;
+
+    #[test]
+    fn it_parses_css() {
+        let clamps = Clamps::default();
+        let (input, color) = rgb_space(
+            "color(display-p3 1 0 0 / 0.5)",
+            RgbSpace::DisplayP3,
+            &clamps,
+        )
+        .unwrap();
+        assert_eq!("", input);
+        assert_eq!(0.5, color.alpha);
+        assert!(!color.is_in_srgb_gamut());
+    }
+
+    #[test]
+    fn it_parses_plain_values() {
+        let clamps = Clamps::default();
+        let (input, color) = rgb_space("100%, 100%, 100%", RgbSpace::Rec2020, &clamps).unwrap();
+        assert_eq!("", input);
+        assert!((color.red - 1.0).abs() < 1e-4);
+        assert!((color.green - 1.0).abs() < 1e-4);
+        assert!((color.blue - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn it_reports_clamped_values() {
+        let clamps = Clamps::default();
+        let input = "color(display-p3 1 0 0 / 1.5)";
+        let (_, color) = rgb_space(input, RgbSpace::DisplayP3, &clamps).unwrap();
+        assert_eq!(1.0, color.alpha);
+        assert_eq!(vec![25..28], clamps.spans(input));
+    }
+}
+
+/// Parses the identifier of a predefined color space, such as `srgb-linear` or `display-p3`.
+///
+/// `xyz` is an alias for `xyz-d65`.
+fn predefined_space(input: &str) -> IResult<&str, PredefinedSpace> {
+    let rgb_space =
+        |space: RgbSpace| value(PredefinedSpace::Rgb(space), tag_no_case(space.css_name()));
+
+    alt((
+        // identifiers sharing a prefix need to be tried longest first
+        value(PredefinedSpace::SrgbLinear, tag_no_case("srgb-linear")),
+        value(PredefinedSpace::Srgb, tag_no_case("srgb")),
+        value(PredefinedSpace::XyzD50, tag_no_case("xyz-d50")),
+        value(PredefinedSpace::XyzD65, tag_no_case("xyz-d65")),
+        value(PredefinedSpace::XyzD65, tag_no_case("xyz")),
+        rgb_space(RgbSpace::DisplayP3),
+        rgb_space(RgbSpace::Rec2020),
+        rgb_space(RgbSpace::AdobeRgb),
+        rgb_space(RgbSpace::ProPhotoRgb),
+    ))(input)
+}
+
+/// Parses a component of the CSS `color()` function, which is either a number, a percentage or `none`.
+///
+/// Missing components specified with `none` are treated as zero, like browsers do when rendering them.
+fn color_function_component(input: &str) -> IResult<&str, f32> {
+    alt((
+        value(0.0, tag_no_case("none")),
+        parse_percentage,
+        nom::number::complete::float,
+    ))(input)
+}
+
+/// Parses a color using the CSS Color Level 4 `color()` function, such as
+/// `color(xyz-d50 0.4 0.3 none / 50%)`.
+///
+/// All predefined color spaces are supported: `srgb`, `srgb-linear`, `display-p3`, `a98-rgb`,
+/// `prophoto-rgb`, `rec2020`, `xyz-d50` and `xyz-d65`/`xyz`. Components outside of the range of
+/// the color space are kept, so colors outside of the sRGB gamut are not clamped.
+pub fn color_function<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("color("))(input)?;
+    let (input, space) = whitespace(predefined_space)(input)?;
+
+    let (input, components) = many_m_n(3, 3, whitespace(color_function_component))(input)?;
+    let (input, alpha) = opt(preceded(
+        whitespace(tag("/")),
+        whitespace(clamped(0.0, 1.0, clamps, color_function_component)),
+    ))(input)?;
+
+    let (input, _) = whitespace(tag(")"))(input)?;
+
+    let color = space.color(
+        [components[0], components[1], components[2]],
+        alpha.unwrap_or(1.0),
+    );
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_color_function {
+    use super::*;
+
+    fn assert_components(color: Color, expected: [f32; 4]) {
+        let actual = [color.red, color.green, color.blue, color.alpha];
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-4,
+                "expected {expected:?}, got {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_parses_srgb() {
+        let clamps = Clamps::default();
+        let (input, color) = color_function("color(srgb 0.25 0.5 0.75 / 0.5)", &clamps).unwrap();
+        assert_eq!("", input);
+        assert_components(color, [0.25, 0.5, 0.75, 0.5]);
+    }
+
+    #[test]
+    fn it_parses_none_and_percentages() {
+        let (_, color) =
+            color_function("color(SRGB 100% none 50% / none)", &Clamps::default()).unwrap();
+        assert_components(color, [1.0, 0.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn it_parses_srgb_linear() {
+        let (_, color) =
+            color_function("color(srgb-linear 0.214041 0 1)", &Clamps::default()).unwrap();
+        assert_components(color, [0.5, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn it_parses_xyz() {
+        let (_, color) = color_function(
+            "color(xyz-d65 0.412456 0.212673 0.019334)",
+            &Clamps::default(),
+        )
+        .unwrap();
+        assert_components(color, [1.0, 0.0, 0.0, 1.0]);
+
+        let (_, color) =
+            color_function("color(xyz 0.412456 0.212673 0.019334)", &Clamps::default()).unwrap();
+        assert_components(color, [1.0, 0.0, 0.0, 1.0]);
+
+        let (_, color) =
+            color_function("color(xyz-d50 0.96422 1 0.82521)", &Clamps::default()).unwrap();
+        assert_components(color, [1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn it_parses_rgb_spaces() {
+        let (_, color) = color_function("color(display-p3 1 0 0)", &Clamps::default()).unwrap();
+        assert_eq!(RgbSpace::DisplayP3.color([1.0, 0.0, 0.0], 1.0), color);
+
+        let (_, color) = color_function("color(rec2020 1 1 1)", &Clamps::default()).unwrap();
+        assert_components(color, [1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn it_keeps_out_of_gamut_colors() {
+        let (_, color) = color_function("color(srgb 1.2 -0.1 0.5)", &Clamps::default()).unwrap();
+        assert!(!color.is_in_srgb_gamut());
+        assert_components(color, [1.2, -0.1, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn it_rejects_unknown_spaces() {
+        assert!(color_function("color(cmyk 0 0 0)", &Clamps::default()).is_err());
+        assert!(color_function("color(srgb 0 0)", &Clamps::default()).is_err());
+    }
+
+    #[test]
+    fn it_reports_clamped_values() {
+        let clamps = Clamps::default();
+        let input = "color(rec2020 1 0 0 / 150%)";
+        let (_, color) = color_function(input, &clamps).unwrap();
+        assert_eq!(1.0, color.alpha);
+        assert_eq!(vec![22..26], clamps.spans(input));
+    }
+}
+
+/// Parses the components of a HSL-like color, such as HSLuv or OKHSL, following the given function name.
+///
+/// The saturation and lightness can either be a percentage or a number between 0 and 100.
+fn hsl_components<'a>(
+    input: &'a str,
+    function_name: &'static str,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, (f32, f32, f32, f32)> {
+    let (input, _) = whitespace(tag_no_case(function_name))(input)?;
+
+    let (input, hue) = terminated(whitespace(hue), opt(whitespace(separator)))(input)?;
+
+    let (input, color_values) = many_m_n(
+        2,
+        2,
+        terminated(
+            whitespace(alt((
+                map(parse_percentage, |percentage| percentage * 100.0),
+                nom::number::complete::float,
+            ))),
+            opt(whitespace(separator)),
+        ),
+    )(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    Ok((
+        input,
+        (hue, color_values[0], color_values[1], alpha.unwrap_or(1.0)),
+    ))
+}
+
+/// Parses a HSLuv representation of a color, such as `hsluv(250.72, 25.41%, 21.61%)`.
+pub fn hsluv<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, (hue, saturation, lightness, alpha)) = hsl_components(input, "hsluv(", clamps)?;
+
+    let color =
+        Color::from_palette_unclamped(palette::Hsluva::new(hue, saturation, lightness, alpha));
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_hsluv {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            hsluv("hsluv(250.72, 25.41%, 21.61%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            hsluv("hsluv(250.72deg, 25.41, 21.61, 0.5)", &Clamps::default()),
+        );
+    }
+}
+
+/// Parses a HPLuv representation of a color, such as `hpluv(250.72, 57.17%, 21.61%)`.
+///
+/// Saturations larger than 100% are allowed, as HPLuv does not cover the whole sRGB gamut.
+pub fn hpluv<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, (hue, saturation, lightness, alpha)) = hsl_components(input, "hpluv(", clamps)?;
+
+    let color = Color::from_palette_unclamped(
+        Hpluv::<palette::white_point::D65>::new(hue, saturation, lightness).with_alpha(alpha),
+    );
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_hpluv {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            hpluv("hpluv(250.72, 57.17%, 21.61%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(255, 0, 0),
+            hpluv("hpluv(12.18, 426.75, 53.24)", &Clamps::default()),
+        );
+    }
+}
+
+/// Parses an OKHSL representation of a color, such as `okhsl(264.18, 17.85%, 22.45%)`.
+pub fn okhsl<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, (hue, saturation, lightness, alpha)) = hsl_components(input, "okhsl(", clamps)?;
+
+    let color = Color::from_palette_unclamped(palette::Okhsla::new(
+        hue,
+        saturation / 100.0,
+        lightness / 100.0,
+        alpha,
+    ));
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_okhsl {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            okhsl("okhsl(264.18, 17.85%, 22.45%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            okhsl("okhsl(264.18deg, 17.85, 22.45, 0.5)", &Clamps::default()),
+        );
+    }
+}
+
+/// Parses an OKHSV representation of a color, such as `okhsv(264.18, 22.81%, 26.70%)`.
+pub fn okhsv<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, (hue, saturation, value, alpha)) = hsl_components(input, "okhsv(", clamps)?;
+
+    let color = Color::from_palette_unclamped(palette::Okhsva::new(
+        hue,
+        saturation / 100.0,
+        value / 100.0,
+        alpha,
+    ));
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_okhsv {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            okhsv("okhsv(264.18, 22.81%, 26.70%)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(255, 0, 0),
+            okhsv("okhsv(29.23, 100%, 100%)", &Clamps::default()),
+        );
+    }
+}
+
+/// Parses a CIELUV representation of a color, such as `luv(21.61, -3.21, -9.19)`.
+///
+/// The lightness can either be a percentage or a number between 0 and 100.
+pub fn cieluv<'a>(
+    input: &'a str,
+    reference_white: ReferenceWhite,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(alt((tag_no_case("luv("), tag_no_case("cieluv("))))(input)?;
+
+    let (input, lightness) = terminated(
+        whitespace(alt((
+            map(parse_percentage, |percentage| percentage * 100.0),
+            nom::number::complete::float,
+        ))),
+        opt(whitespace(separator)),
+    )(input)?;
+
+    let (input, u_v) = many_m_n(
+        2,
+        2,
+        terminated(
+            whitespace(nom::number::complete::float),
+            opt(whitespace(separator)),
+        ),
+    )(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let color = Color::from_luv(
+        palette::Luva::new(lightness, u_v[0], u_v[1], alpha.unwrap_or(1.0)),
+        reference_white,
+    );
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_cieluv {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            cieluv(
+                "luv(21.61, -3.21, -9.19)",
+                ReferenceWhite::default(),
+                &Clamps::default(),
+            ),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            cieluv(
+                "cieluv(21.61%, -3.21, -9.19, 0.5)",
+                ReferenceWhite::default(),
+                &Clamps::default(),
+            ),
+        );
+    }
+}
+
+/// Parses a CIELCh(uv) representation of a color, the cylindrical form of CIELUV.
+///
+/// The lightness can either be a percentage or a number between 0 and 100.
+pub fn lchuv<'a>(
+    input: &'a str,
+    reference_white: ReferenceWhite,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("lchuv("))(input)?;
+
+    let (input, lightness) = terminated(
+        whitespace(alt((
+            map(parse_percentage, |percentage| percentage * 100.0),
+            nom::number::complete::float,
+        ))),
+        opt(whitespace(separator)),
+    )(input)?;
+
+    let (input, chroma) = terminated(
+        whitespace(nom::number::complete::float),
+        opt(whitespace(separator)),
+    )(input)?;
+
+    let (input, hue) = terminated(whitespace(hue), opt(whitespace(separator)))(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let luv: palette::Luva<palette::white_point::Any> =
+        palette::Lchuva::new(lightness, chroma, hue, alpha.unwrap_or(1.0)).into_color_unclamped();
+    let color = Color::from_luv(luv, reference_white);
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_lchuv {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            lchuv(
+                "lchuv(21.61, 9.73, 250.72)",
+                ReferenceWhite::default(),
+                &Clamps::default(),
+            ),
+        );
+    }
+}
+
+/// Parses a Jzazbz representation of a color, such as `jzazbz(0.0507 -0.0032 -0.0174)`.
+///
+/// The values are converted using the given absolute luminance of the SDR white in cd/m².
+pub fn jzazbz<'a>(
+    input: &'a str,
+    white_luminance: f32,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("jzazbz("))(input)?;
+
+    let (input, jab) = many_m_n(
+        3,
+        3,
+        terminated(
+            whitespace(nom::number::complete::float),
+            opt(whitespace(separator)),
+        ),
+    )(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let xyz = Jzazbz::new(jab[0], jab[1], jab[2]).into_xyz(white_luminance);
+    let color = Color::from_palette_unclamped(xyz.with_alpha(alpha.unwrap_or(1.0)));
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_jzazbz {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            jzazbz("jzazbz(0.0507 -0.0032 -0.0174)", 203.0, &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            jzazbz(
+                "jzazbz(0.0507, -0.0032, -0.0174, 0.5)",
+                203.0,
+                &Clamps::default(),
+            ),
+        );
+    }
+}
+
+/// Parses a JzCzhz representation of a color, the cylindrical form of Jzazbz.
+///
+/// The values are converted using the given absolute luminance of the SDR white in cd/m².
+pub fn jzczhz<'a>(
+    input: &'a str,
+    white_luminance: f32,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("jzczhz("))(input)?;
+
+    let (input, lightness) = terminated(
+        whitespace(nom::number::complete::float),
+        opt(whitespace(separator)),
+    )(input)?;
+
+    let (input, chroma) = terminated(
+        whitespace(nom::number::complete::float),
+        opt(whitespace(separator)),
+    )(input)?;
+
+    let (input, hue) = terminated(whitespace(hue), opt(whitespace(separator)))(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let jzazbz: Jzazbz = Jzczhz::new(lightness, chroma, hue).into_color_unclamped();
+    let xyz = jzazbz.into_xyz(white_luminance);
+    let color = Color::from_palette_unclamped(xyz.with_alpha(alpha.unwrap_or(1.0)));
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_jzczhz {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            jzczhz("jzczhz(0.0507 0.0177 259.72)", 203.0, &Clamps::default()),
+        );
+    }
+}
+
+/// Parses an ICtCp representation of a color, such as `ictcp(0.2722 0.0345 -0.0181)`.
+///
+/// The values are converted using the given absolute luminance of the SDR white in cd/m².
+pub fn ictcp<'a>(
+    input: &'a str,
+    white_luminance: f32,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("ictcp("))(input)?;
+
+    let (input, ictcp) = many_m_n(
+        3,
+        3,
+        terminated(
+            whitespace(nom::number::complete::float),
+            opt(whitespace(separator)),
+        ),
+    )(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let xyz = Ictcp::new(ictcp[0], ictcp[1], ictcp[2]).into_xyz(white_luminance);
+    let color = Color::from_palette_unclamped(xyz.with_alpha(alpha.unwrap_or(1.0)));
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_ictcp {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            ictcp("ictcp(0.2722 0.0345 -0.0181)", 203.0, &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            ictcp("ictcp(0.6365 0.0481 -0.0258)", 10000.0, &Clamps::default()),
+        );
+    }
+}
+
+/// Parses a CAM16 representation of a color, consisting of the lightness (J), chroma (C) and hue (h),
+/// such as `cam16(15.53, 11.97, 262.80)`.
+///
+/// The values are converted using the given viewing conditions.
+pub fn cam16<'a>(
+    input: &'a str,
+    viewing_conditions: ViewingConditions,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(tag_no_case("cam16("))(input)?;
+
+    let (input, lightness) = terminated(
+        whitespace(alt((
+            map(parse_percentage, |percentage| percentage * 100.0),
+            nom::number::complete::float,
+        ))),
+        opt(whitespace(separator)),
+    )(input)?;
+
+    let (input, chroma) = terminated(
+        whitespace(nom::number::complete::float),
+        opt(whitespace(separator)),
+    )(input)?;
+
+    let (input, hue) = terminated(whitespace(hue), opt(whitespace(separator)))(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let cam16 = Cam16Jch::new(lightness as f64, chroma as f64, hue as f64)
+        .into_full(viewing_conditions.parameters());
+    let color = Color::from_cam16(cam16, alpha.unwrap_or(1.0), viewing_conditions);
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_cam16 {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            cam16(
+                "cam16(15.53, 11.97, 262.80)",
+                ViewingConditions::default(),
+                &Clamps::default(),
+            ),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            cam16(
+                "cam16(15.53 11.97 262.8deg / 0.5)",
+                ViewingConditions::default(),
+                &Clamps::default(),
+            ),
+        );
+    }
+}
+
+/// Parses a CAM16-UCS representation of a color, consisting of the lightness (J') and
+/// the a' and b' coordinates, such as `cam16ucs(23.81, -1.10, -8.69)`.
+///
+/// The values are converted using the given viewing conditions.
+pub fn cam16_ucs<'a>(
+    input: &'a str,
+    viewing_conditions: ViewingConditions,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = whitespace(alt((tag_no_case("cam16ucs("), tag_no_case("cam16-ucs("))))(input)?;
+
+    let (input, lightness) = terminated(
+        whitespace(alt((
+            map(parse_percentage, |percentage| percentage * 100.0),
+            nom::number::complete::float,
+        ))),
+        opt(whitespace(separator)),
+    )(input)?;
+
+    let (input, a_b) = many_m_n(
+        2,
+        2,
+        terminated(
+            whitespace(nom::number::complete::float),
+            opt(whitespace(separator)),
+        ),
+    )(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let jmh: Cam16Jmh<f64> =
+        Cam16UcsJab::new(lightness as f64, a_b[0] as f64, a_b[1] as f64).into_color_unclamped();
+    let cam16 = jmh.into_full(viewing_conditions.parameters());
+    let color = Color::from_cam16(cam16, alpha.unwrap_or(1.0), viewing_conditions);
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_cam16_ucs {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            cam16_ucs(
+                "cam16ucs(23.81, -1.10, -8.69)",
+                ViewingConditions::default(),
+                &Clamps::default(),
+            ),
+        );
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            cam16_ucs(
+                "cam16-ucs(23.81% -1.10 -8.69)",
+                ViewingConditions::default(),
+                &Clamps::default(),
+            ),
+        );
+    }
+}
+
+/// Parses YCbCr codes, such as `ycbcr(60, 134, 125)`, using the given encoding.
+///
+/// The function name is optional, so plain triplets like `60 134 125` are accepted as well.
+pub fn ycbcr<'a>(
+    input: &'a str,
+    encoding: YcbcrEncoding,
+    clamps: &'a Clamps,
+) -> IResult<&'a str, Color> {
+    let (input, _) = opt(whitespace(alt((
+        tag_no_case("ycbcr("),
+        tag_no_case("yuv("),
+    ))))(input)?;
+
+    let (input, codes) = many_m_n(
+        3,
+        3,
+        terminated(
+            whitespace(nom::number::complete::float),
+            opt(whitespace(separator)),
+        ),
+    )(input)?;
+
+    let (input, alpha) = opt(whitespace(alpha_value(clamps)))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let color = encoding.color([codes[0], codes[1], codes[2]], alpha.unwrap_or(1.0));
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_ycbcr {
+    use super::*;
+    use crate::colors::ycbcr::{YcbcrMatrix, YcbcrRange};
+
+    #[test]
+    fn it_parses() {
+        let encoding = YcbcrEncoding::default();
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            ycbcr("ycbcr(60, 134, 125)", encoding, &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            ycbcr("60 134 125", encoding, &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            ycbcr("yuv(60,134,125)", encoding, &Clamps::default()),
+        );
+    }
+
+    #[test]
+    fn it_parses_full_range_10_bit() {
+        let encoding = YcbcrEncoding {
+            matrix: YcbcrMatrix::Bt601,
+            range: YcbcrRange::Full,
+            bit_depth: 10,
+        };
+        assert_rgba8(
+            Color::rgb(255, 0, 0),
+            ycbcr("306, 339, 1023", encoding, &Clamps::default()),
+        );
+    }
+}
+
+/// Parses a correlated color temperature in Kelvin, optionally followed by the distance
+/// from the Planckian locus, such as `6500K` or `6505K, Duv 0.0033`.
+///
+/// Only temperatures between 1000K and 15000K are accepted. As the temperature does not
+/// contain the luminance, the brightest color with this temperature is returned.
+pub fn cct(input: &str) -> IResult<&str, Color> {
+    let (input, _) = opt(whitespace(tag_no_case("cct(")))(input)?;
+
+    let (input, temperature) = terminated(
+        whitespace(verify(nom::number::complete::float, |temperature| {
+            (cct::MIN_TEMPERATURE..=cct::MAX_TEMPERATURE).contains(temperature)
+        })),
+        opt(whitespace(tag_no_case("k"))),
+    )(input)?;
+
+    let (input, duv) = opt(preceded(
+        opt(whitespace(separator)),
+        preceded(
+            whitespace(tag_no_case("duv")),
+            preceded(
+                opt(whitespace(alt((tag(":"), tag("="))))),
+                // once the Duv has been started, it has to be valid
+                cut(whitespace(verify(
+                    nom::number::complete::float,
+                    |duv: &f32| duv.abs() <= cct::MAX_DUV,
+                ))),
+            ),
+        ),
+    ))(input)?;
+
+    let (input, _) = opt(whitespace(tag(")")))(input)?;
+
+    let color = Cct {
+        temperature,
+        duv: duv.unwrap_or(0.0),
+    }
+    .color();
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_cct {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(Color::rgb(255, 248, 254), cct("6500K"));
+        assert_rgba8(Color::rgb(255, 255, 255), cct("6505K, Duv 0.0033"));
+        assert_rgba8(Color::rgb(255, 255, 255), cct("cct(6505 k duv=0.0033)"));
+    }
+
+    #[test]
+    fn it_rejects_temperatures_outside_the_locus() {
+        assert!(cct("500K").is_err());
+        assert!(cct("6500K, Duv 0.2").is_err());
+    }
+}
+
+/// Parses a tuple of three or four floating point values, such as `0.2158, 0.0331, 0.5089`,
+/// which may be enclosed in parentheses, brackets or braces.
+///
+/// A missing fourth value is treated as fully opaque alpha.
+fn float_tuple<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, [f32; 4]> {
+    let (input, _) = opt(whitespace(alt((tag("("), tag("["), tag("{")))))(input)?;
+
+    let (input, values) = many_m_n(
+        3,
+        3,
+        terminated(
+            whitespace(nom::number::complete::float),
+            opt(whitespace(separator)),
+        ),
+    )(input)?;
+    let (input, alpha) = opt(terminated(
+        whitespace(clamped(0.0, 1.0, clamps, nom::number::complete::float)),
+        opt(whitespace(separator)),
+    ))(input)?;
+
+    let (input, _) = opt(whitespace(alt((tag(")"), tag("]"), tag("}")))))(input)?;
+
+    Ok((
+        input,
+        [values[0], values[1], values[2], alpha.unwrap_or(1.0)],
+    ))
+}
+
+/// Parses a tuple of linear sRGB values, as used by shaders and 3D tools.
+pub fn linear_floats<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, [red, green, blue, alpha]) = float_tuple(input, clamps)?;
+    let color = Color::from_palette_unclamped(LinSrgba::new(red, green, blue, alpha));
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_linear_floats {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            linear_floats("0.0273, 0.0343, 0.0513", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            linear_floats("(0.0273 0.0343 0.0513 0.5)", &Clamps::default()),
+        );
+    }
+}
+
+/// Parses a tuple of gamma encoded sRGB values between 0 and 1.
+pub fn floats<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, [red, green, blue, alpha]) = float_tuple(input, clamps)?;
+    Ok((input, Color::new(red, green, blue, alpha)))
+}
+
+#[cfg(test)]
+mod parse_floats {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            floats("0.1804, 0.2039, 0.2510", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            floats("[0.1804, 0.2039, 0.2510, 0.5]", &Clamps::default()),
+        );
+    }
+
+    #[test]
+    fn it_reports_clamped_values() {
+        let clamps = Clamps::default();
+        let input = "0.1804, 0.2039, 0.2510, 1.5";
+        assert_rgba8(Color::rgb(46, 52, 64), floats(input, &clamps));
+        assert_eq!(vec![24..27], clamps.spans(input));
+    }
+}
+
+/// Parses a floating point literal, which may end with the `f` suffix used by C-like languages, such as `0.5f`.
+fn float_literal(input: &str) -> IResult<&str, f32> {
+    terminated(nom::number::complete::float, opt(tag_no_case("f")))(input)
+}
+
+/// Parses an 8-bit integer literal, such as `128`.
+fn integer_literal(input: &str) -> IResult<&str, u8> {
+    map_res(digit1, str::parse::<u8>)(input)
+}
+
+/// Parses the closing parenthesis of a function call.
+fn closing_parenthesis(input: &str) -> IResult<&str, &str> {
+    expect(Expectation::ClosingParenthesis, whitespace(tag(")")))(input)
+}
+
+/// Parses a hex color with six or eight digits, where the alpha value comes first, such as `FF2E3440`.
+fn argb_hex(input: &str) -> IResult<&str, Color> {
+    let (input, digits) = verify(
+        take_while_m_n(6, 8, |char: char| char.is_ascii_hexdigit()),
+        |digits: &str| digits.len() != 7,
+    )(input)?;
+
+    let (_, alpha) = if digits.len() == 8 {
+        hex(digits)?
+    } else {
+        (digits, 255)
+    };
+    let (_, (red, green, blue)) = (hex, hex, hex).parse(&digits[digits.len() - 6..])?;
+
+    Ok((input, Color::rgba(red, green, blue, alpha)))
+}
+
+/// Parses a named float argument of a Swift initializer, such as `red: 0.5`.
+fn swift_argument<'a>(label: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, f32> {
+    delimited(
+        whitespace(pair(tag(label), whitespace(tag(":")))),
+        whitespace(float_literal),
+        opt(whitespace(separator)),
+    )
+}
+
+/// Parses the float red, green and blue arguments of a Swift initializer.
+fn swift_rgb(input: &str) -> IResult<&str, (f32, f32, f32)> {
+    (
+        swift_argument("red"),
+        swift_argument("green"),
+        swift_argument("blue"),
+    )
+        .parse(input)
+}
+
+/// Parses a Swift UIKit color, such as `UIColor(red: 0.1804, green: 0.2039, blue: 0.2510, alpha: 1.0000)`.
+pub fn uicolor(input: &str) -> IResult<&str, Color> {
+    let (input, _) = whitespace(tag("UIColor("))(input)?;
+    let (input, (red, green, blue)) = swift_rgb(input)?;
+    let (input, alpha) = opt(swift_argument("alpha"))(input)?;
+    let (input, _) = closing_parenthesis(input)?;
+
+    Ok((input, Color::new(red, green, blue, alpha.unwrap_or(1.0))))
+}
+
+#[cfg(test)]
+mod parse_uicolor {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            uicolor("UIColor(red: 0.1804, green: 0.2039, blue: 0.2510, alpha: 0.5)"),
+        );
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            uicolor("UIColor(red:0.1804,green:0.2039,blue:0.2510)"),
+        );
+    }
+}
+
+/// Parses a SwiftUI color, such as `Color(red: 0.1804, green: 0.2039, blue: 0.2510, opacity: 0.5000)`.
+///
+/// The color may specify the `.sRGB` color space.
+pub fn swiftui(input: &str) -> IResult<&str, Color> {
+    let (input, _) = whitespace(tag("Color("))(input)?;
+    let (input, _) = opt(terminated(
+        whitespace(tag(".sRGB")),
+        opt(whitespace(separator)),
+    ))(input)?;
+    let (input, (red, green, blue)) = swift_rgb(input)?;
+    let (input, alpha) = opt(swift_argument("opacity"))(input)?;
+    let (input, _) = closing_parenthesis(input)?;
+
+    Ok((input, Color::new(red, green, blue, alpha.unwrap_or(1.0))))
+}
+
+#[cfg(test)]
+mod parse_swiftui {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            swiftui("Color(red: 0.1804, green: 0.2039, blue: 0.2510)"),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            swiftui("Color(.sRGB, red: 0.1804, green: 0.2039, blue: 0.2510, opacity: 0.5)"),
+        );
+    }
+}
+
+/// Parses a Jetpack Compose color, such as `Color(0xFF2E3440)`.
+pub fn compose(input: &str) -> IResult<&str, Color> {
+    delimited(
+        whitespace(pair(tag("Color("), whitespace(tag_no_case("0x")))),
+        argb_hex,
+        closing_parenthesis,
+    )(input)
+}
+
+#[cfg(test)]
+mod parse_compose {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(Color::rgb(46, 52, 64), compose("Color(0xFF2E3440)"));
+        assert_rgba8(Color::rgba(46, 52, 64, 128), compose("Color(0x802e3440)"));
+    }
+}
+
+/// Parses a Flutter color, such as `const Color(0xFF2E3440)`.
+pub fn flutter(input: &str) -> IResult<&str, Color> {
+    preceded(opt(whitespace(tag("const"))), compose)(input)
+}
+
+#[cfg(test)]
+mod parse_flutter {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(Color::rgb(46, 52, 64), flutter("const Color(0xFF2E3440)"));
+        assert_rgba8(Color::rgba(46, 52, 64, 128), flutter("Color(0x802E3440)"));
+    }
+}
+
+/// Parses an Android color resource, such as `<color name="color_2e3440">#FF2E3440</color>`.
+///
+/// The alpha value comes first, following the format used by Android.
+pub fn android_xml(input: &str) -> IResult<&str, Color> {
+    let (input, _) = opt(whitespace(delimited(
+        tag("<color"),
+        take_while(|char| char != '>'),
+        tag(">"),
+    )))(input)?;
+    let (input, color) = preceded(whitespace(tag("#")), argb_hex)(input)?;
+    let (input, _) = opt(whitespace(tag("</color>")))(input)?;
+
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_android_xml {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            android_xml(r##"<color name="color_2e3440">#2E3440</color>"##),
+        );
+        assert_rgba8(Color::rgba(46, 52, 64, 128), android_xml("#802E3440"));
+    }
+}
+
+/// Parses the three or four integer arguments of a function call, such as `46, 52, 64)`.
+fn integer_arguments(input: &str) -> IResult<&str, Vec<u8>> {
+    terminated(
+        many_m_n(
+            3,
+            4,
+            terminated(whitespace(integer_literal), opt(whitespace(separator))),
+        ),
+        closing_parenthesis,
+    )(input)
+}
+
+/// Parses a Qt color, such as `QColor(46, 52, 64, 128)`, where the alpha value comes last.
+pub fn qcolor(input: &str) -> IResult<&str, Color> {
+    let (input, values) = preceded(whitespace(tag("QColor(")), integer_arguments)(input)?;
+    let alpha = values.get(3).copied().unwrap_or(255);
+    Ok((input, Color::rgba(values[0], values[1], values[2], alpha)))
+}
+
+#[cfg(test)]
+mod parse_qcolor {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(Color::rgb(46, 52, 64), qcolor("QColor(46, 52, 64)"));
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            qcolor("QColor(46, 52, 64, 128)"),
+        );
+    }
+}
+
+/// Parses a C# color, such as `Color.FromArgb(128, 46, 52, 64)`, where the alpha value comes first.
+pub fn csharp(input: &str) -> IResult<&str, Color> {
+    let (input, values) = preceded(whitespace(tag("Color.FromArgb(")), integer_arguments)(input)?;
+    let color = match values[..] {
+        [alpha, red, green, blue] => Color::rgba(red, green, blue, alpha),
+        _ => Color::rgb(values[0], values[1], values[2]),
+    };
+    Ok((input, color))
+}
+
+#[cfg(test)]
+mod parse_csharp {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(Color::rgb(46, 52, 64), csharp("Color.FromArgb(46, 52, 64)"));
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            csharp("Color.FromArgb(128, 46, 52, 64)"),
+        );
+    }
+}
+
+/// Parses a Java AWT color, such as `new Color(46, 52, 64, 128)`, where the alpha value comes last.
+pub fn java_awt(input: &str) -> IResult<&str, Color> {
+    let (input, values) = preceded(
+        whitespace(pair(tag("new"), whitespace(tag("Color(")))),
+        integer_arguments,
+    )(input)?;
+    let alpha = values.get(3).copied().unwrap_or(255);
+    Ok((input, Color::rgba(values[0], values[1], values[2], alpha)))
+}
+
+#[cfg(test)]
+mod parse_java_awt {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(Color::rgb(46, 52, 64), java_awt("new Color(46, 52, 64)"));
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            java_awt("new Color(46, 52, 64, 128)"),
+        );
+        assert!(java_awt("new Color(0.1804f, 0.2039f, 0.2510f)").is_err());
+    }
+}
+
+/// Parses the three or four float arguments of a function call, such as `0.5f, 0.5f, 0.5f)`.
+///
+/// A missing fourth value is treated as fully opaque alpha.
+fn float_arguments<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, [f32; 4]> {
+    let (input, values) = many_m_n(
+        3,
+        3,
+        terminated(whitespace(float_literal), opt(whitespace(separator))),
+    )(input)?;
+    let (input, alpha) = terminated(
+        opt(terminated(
+            whitespace(clamped(0.0, 1.0, clamps, float_literal)),
+            opt(whitespace(separator)),
+        )),
+        closing_parenthesis,
+    )(input)?;
+    Ok((
+        input,
+        [values[0], values[1], values[2], alpha.unwrap_or(1.0)],
+    ))
+}
+
+/// Parses a Unity color, such as `new Color(0.1804f, 0.2039f, 0.2510f, 0.5000f)`.
+pub fn unity<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, [red, green, blue, alpha]) = preceded(
+        whitespace(pair(tag("new"), whitespace(tag("Color(")))),
+        |input| float_arguments(input, clamps),
+    )(input)?;
+    Ok((input, Color::new(red, green, blue, alpha)))
+}
+
+#[cfg(test)]
+mod parse_unity {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            unity("new Color(0.1804f, 0.2039f, 0.2510f)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            unity(
+                "new Color(0.1804f, 0.2039f, 0.2510f, 0.5f)",
+                &Clamps::default(),
+            ),
+        );
+    }
+}
+
+/// Parses a GLSL vector, such as `vec4(0.1804, 0.2039, 0.2510, 0.5000)` or `vec3(0.1804, 0.2039, 0.2510)`.
+pub fn glsl<'a>(input: &'a str, clamps: &'a Clamps) -> IResult<&'a str, Color> {
+    let (input, [red, green, blue, alpha]) =
+        preceded(whitespace(alt((tag("vec3("), tag("vec4(")))), |input| {
+            float_arguments(input, clamps)
+        })(input)?;
+    Ok((input, Color::new(red, green, blue, alpha)))
+}
+
+#[cfg(test)]
+mod parse_glsl {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            glsl("vec3(0.1804, 0.2039, 0.2510)", &Clamps::default()),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            glsl("vec4(0.1804, 0.2039, 0.2510, 0.5)", &Clamps::default()),
+        );
+    }
+
+    #[test]
+    fn it_reports_clamped_values() {
+        let clamps = Clamps::default();
+        let input = "vec4(0.1804, 0.2039, 0.2510, 1.5)";
+        assert_rgba8(Color::rgb(46, 52, 64), glsl(input, &clamps));
+        assert_eq!(vec![29..32], clamps.spans(input));
+    }
+}
+
+/// Parses an unsigned integer, either in hexadecimal with the `0x` prefix or in decimal,
+/// such as `0x2E3440` or `3028032`.
+fn packed_integer(input: &str) -> IResult<&str, u32> {
+    whitespace(alt((
+        preceded(
+            tag_no_case("0x"),
+            map_res(
+                take_while_m_n(1, 8, |char: char| char.is_ascii_hexdigit()),
+                |digits| u32::from_str_radix(digits, 16),
+            ),
+        ),
+        map_res(digit1, str::parse::<u32>),
+    )))(input)
+}
+
+/// Parses a 16-bit integer, which may be followed by the decoded color in parentheses,
+/// such as `0x31A8 (#313542)`.
+fn packed_u16(input: &str) -> IResult<&str, u16> {
+    terminated(
+        map_res(packed_integer, u16::try_from),
+        opt(whitespace(delimited(
+            tag("("),
+            take_while(|char: char| char != ')'),
+            tag(")"),
+        ))),
+    )(input)
+}
+
+/// Parses a color packed into a single integer, such as `0x2E3440` or `3028032`,
+/// with the alpha value at the given position.
+///
+/// Without an alpha value, integers larger than 24 bits are rejected.
+pub fn rgb_integer(input: &str, alpha_position: AlphaPosition) -> IResult<&str, Color> {
+    map_opt(packed_integer, |value| {
+        packed::from_rgb(value, alpha_position)
+    })(input)
+}
+
+#[cfg(test)]
+mod parse_rgb_integer {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            rgb_integer("3028032", AlphaPosition::None),
+        );
+        assert_rgba8(
+            Color::rgb(46, 52, 64),
+            rgb_integer("0x2E3440", AlphaPosition::None),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            rgb_integer("0x802E3440", AlphaPosition::Start),
+        );
+        assert_rgba8(
+            Color::rgba(46, 52, 64, 128),
+            rgb_integer("0x2e344080", AlphaPosition::End),
+        );
+        assert!(rgb_integer("0x802E3440", AlphaPosition::None).is_err());
+    }
+}
+
+/// Parses a Win32 `COLORREF`, such as `0x0040342E` or `4207662`.
+pub fn colorref(input: &str) -> IResult<&str, Color> {
+    map(packed_integer, packed::from_colorref)(input)
+}
+
+#[cfg(test)]
+mod parse_colorref {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(Color::rgb(46, 52, 64), colorref("0x0040342E"));
+        assert_rgba8(Color::rgb(46, 52, 64), colorref("4207662"));
+    }
+}
+
+/// Parses a 16-bit RGB565 color, such as `0x31A8` or `12712`.
+pub fn rgb565(input: &str) -> IResult<&str, Color> {
+    map(packed_u16, packed::from_rgb565)(input)
+}
+
+#[cfg(test)]
+mod parse_rgb565 {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(Color::rgb(49, 53, 66), rgb565("0x31A8"));
+        assert_rgba8(Color::rgb(49, 53, 66), rgb565("12712"));
+        assert_rgba8(Color::rgb(49, 53, 66), rgb565("0x31A8 (#313542)"));
+        assert!(rgb565("0x10000").is_err());
+    }
+}
+
+/// Parses a 16-bit RGB555 color, such as `0x18C8`, with the alpha bit at the given position.
+pub fn rgb555(input: &str, alpha_position: AlphaPosition) -> IResult<&str, Color> {
+    map(packed_u16, |value| {
+        packed::from_rgb555(value, alpha_position)
+    })(input)
+}
+
+#[cfg(test)]
+mod parse_rgb555 {
+    use super::*;
+
+    #[test]
+    fn it_parses() {
+        assert_rgba8(
+            Color::rgb(49, 49, 66),
+            rgb555("0x18C8 (#313142)", AlphaPosition::None),
+        );
+        assert_rgba8(
+            Color::rgba(49, 49, 66, 0),
+            rgb555("0x18C8", AlphaPosition::Start),
         );
+        assert_rgba8(Color::rgb(49, 49, 66), rgb555("0x3191", AlphaPosition::End));
     }
 }
@@ -0,0 +1,49 @@
+//! The perceptual quantizer (PQ) transfer function of SMPTE ST 2084, used by HDR color spaces.
+
+/// The luminance in cd/m², that is encoded as 1.0.
+pub const PEAK_LUMINANCE: f32 = 10000.0;
+/// The default luminance of SDR white in cd/m², as recommended by ITU-R BT.2408.
+pub const SDR_WHITE_LUMINANCE: f32 = 203.0;
+
+const M1: f64 = 2610.0 / 16384.0;
+/// The exponent used by ST 2084, Jzazbz uses a different one.
+pub const M2: f64 = 2523.0 / 4096.0 * 128.0;
+const C1: f64 = 3424.0 / 4096.0;
+const C2: f64 = 2413.0 / 4096.0 * 32.0;
+const C3: f64 = 2392.0 / 4096.0 * 32.0;
+
+/// Converts a linear value, relative to the peak luminance, into a perceptually uniform one.
+///
+/// Negative values are mirrored, so out of gamut values can be converted back.
+pub fn encode(linear: f32, m2: f64) -> f32 {
+    let value = (linear.abs() as f64).powf(M1);
+    let encoded = ((C1 + C2 * value) / (1.0 + C3 * value)).powf(m2);
+    (encoded as f32).copysign(linear)
+}
+
+/// Inverts [`encode`], converting a perceptually uniform value into a linear one.
+pub fn decode(encoded: f32, m2: f64) -> f32 {
+    let value = (encoded.abs() as f64).powf(1.0 / m2);
+    let linear = ((value - C1).max(0.0) / (C2 - C3 * value)).powf(1.0 / M1);
+    (linear as f32).copysign(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_reference_values() {
+        assert!(encode(0.0, M2).abs() < 1e-6);
+        assert!((encode(1.0, M2) - 1.0).abs() < 1e-6);
+        // 100 cd/m² are encoded as roughly 50.8%
+        assert!((encode(0.01, M2) - 0.5081).abs() < 1e-4);
+    }
+
+    #[test]
+    fn round_trips() {
+        for value in [-0.5, 0.0001, 0.0203, 0.7] {
+            assert!((decode(encode(value, M2), M2) - value).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use palette::{white_point::Any, WithAlpha, Xyz};
+
+use super::{
+    color::Color,
+    illuminant::{Illuminant, Observer, ReferenceWhite},
+    matrix::{self, Matrix3},
+};
+
+/// An RGB color space with a wider gamut than sRGB.
+///
+/// The components are gamma encoded and not limited to `0.0..=1.0`, so colors
+/// outside of the gamut of the space are preserved.
+///
+/// The primaries and transfer functions follow the [CSS Color Module Level 4](https://www.w3.org/TR/css-color-4/#predefined).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RgbSpace {
+    /// Display P3, used by most modern displays.
+    DisplayP3,
+    /// ITU-R BT.2020, used for UHDTV.
+    Rec2020,
+    /// Adobe RGB (1998), used in print workflows.
+    AdobeRgb,
+    /// ProPhoto RGB (ROMM RGB), used in photography.
+    ProPhotoRgb,
+}
+
+impl RgbSpace {
+    /// The name used to identify the color space in the CSS `color()` function.
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            RgbSpace::DisplayP3 => "display-p3",
+            RgbSpace::Rec2020 => "rec2020",
+            RgbSpace::AdobeRgb => "a98-rgb",
+            RgbSpace::ProPhotoRgb => "prophoto-rgb",
+        }
+    }
+
+    /// The reference white of the color space.
+    pub fn reference_white(&self) -> ReferenceWhite {
+        match self {
+            RgbSpace::ProPhotoRgb => ReferenceWhite::new(Illuminant::D50, Observer::Degree2),
+            _ => ReferenceWhite::srgb(),
+        }
+    }
+
+    /// The xy chromaticity coordinates of the red, green and blue primaries.
+    pub fn primaries(&self) -> [(f32, f32); 3] {
+        match self {
+            RgbSpace::DisplayP3 => [(0.680, 0.320), (0.265, 0.690), (0.150, 0.060)],
+            RgbSpace::Rec2020 => [(0.708, 0.292), (0.170, 0.797), (0.131, 0.046)],
+            RgbSpace::AdobeRgb => [(0.640, 0.330), (0.210, 0.710), (0.150, 0.060)],
+            RgbSpace::ProPhotoRgb => [
+                (0.734699, 0.265301),
+                (0.159597, 0.840403),
+                (0.036598, 0.000105),
+            ],
+        }
+    }
+
+    /// Returns the matrix to convert linear RGB values into XYZ values.
+    ///
+    /// The matrix is derived from the primaries and the reference white, as described
+    /// in <http://brucelindbloom.com/index.html?Eqn_RGB_XYZ_Matrix.html>.
+    pub(super) fn rgb_to_xyz_matrix(&self) -> Matrix3 {
+        let [red, green, blue] = self
+            .primaries()
+            .map(|(x, y)| [x / y, 1.0, (1.0 - x - y) / y]);
+        let primaries = [0, 1, 2].map(|row| [red[row], green[row], blue[row]]);
+
+        let white = self.reference_white().xyz();
+        let scale = matrix::multiply(matrix::invert(primaries), [white.x, white.y, white.z]);
+
+        primaries.map(|row| [row[0] * scale[0], row[1] * scale[1], row[2] * scale[2]])
+    }
+
+    /// Applies the transfer function, converting a linear value into a gamma encoded one.
+    ///
+    /// Negative values are mirrored, so out of gamut values can be converted back.
+    fn encode(&self, linear: f32) -> f32 {
+        let value = linear.abs();
+        let encoded = match self {
+            RgbSpace::DisplayP3 => {
+                if value <= 0.0031308 {
+                    value * 12.92
+                } else {
+                    1.055 * value.powf(1.0 / 2.4) - 0.055
+                }
+            }
+            RgbSpace::Rec2020 => {
+                const ALPHA: f32 = 1.099_296_8;
+                const BETA: f32 = 0.018_053_97;
+                if value < BETA {
+                    value * 4.5
+                } else {
+                    ALPHA * value.powf(0.45) - (ALPHA - 1.0)
+                }
+            }
+            RgbSpace::AdobeRgb => value.powf(256.0 / 563.0),
+            RgbSpace::ProPhotoRgb => {
+                if value < 1.0 / 512.0 {
+                    value * 16.0
+                } else {
+                    value.powf(1.0 / 1.8)
+                }
+            }
+        };
+        encoded.copysign(linear)
+    }
+
+    /// Inverts the transfer function, converting a gamma encoded value into a linear one.
+    fn decode(&self, encoded: f32) -> f32 {
+        let value = encoded.abs();
+        let linear = match self {
+            RgbSpace::DisplayP3 => {
+                if value <= 0.04045 {
+                    value / 12.92
+                } else {
+                    ((value + 0.055) / 1.055).powf(2.4)
+                }
+            }
+            RgbSpace::Rec2020 => {
+                const ALPHA: f32 = 1.099_296_8;
+                const BETA: f32 = 0.018_053_97;
+                if value < BETA * 4.5 {
+                    value / 4.5
+                } else {
+                    ((value + ALPHA - 1.0) / ALPHA).powf(1.0 / 0.45)
+                }
+            }
+            RgbSpace::AdobeRgb => value.powf(563.0 / 256.0),
+            RgbSpace::ProPhotoRgb => {
+                if value < 16.0 / 512.0 {
+                    value / 16.0
+                } else {
+                    value.powf(1.8)
+                }
+            }
+        };
+        linear.copysign(encoded)
+    }
+
+    /// Returns the gamma encoded red, green and blue components of the color in this color space.
+    pub fn components(&self, color: Color) -> [f32; 3] {
+        let xyz = color.to_xyz(self.reference_white());
+        let linear = matrix::multiply(
+            matrix::invert(self.rgb_to_xyz_matrix()),
+            [xyz.x, xyz.y, xyz.z],
+        );
+        linear.map(|value| self.encode(value))
+    }
+
+    /// Creates a color from the gamma encoded red, green and blue components in this color space.
+    pub fn color(&self, components: [f32; 3], alpha: f32) -> Color {
+        let linear = components.map(|value| self.decode(value));
+        let [x, y, z] = matrix::multiply(self.rgb_to_xyz_matrix(), linear);
+        Color::from_xyz(
+            Xyz::<Any, f32>::new(x, y, z).with_alpha(alpha),
+            self.reference_white(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn white_stays_white() {
+        let white = Color::rgb(255, 255, 255);
+        for space in [
+            RgbSpace::DisplayP3,
+            RgbSpace::Rec2020,
+            RgbSpace::AdobeRgb,
+            RgbSpace::ProPhotoRgb,
+        ] {
+            for component in space.components(white) {
+                assert!((component - 1.0).abs() < 1e-4, "{space:?}: {component}");
+            }
+        }
+    }
+
+    #[test]
+    fn converts_srgb_red_to_display_p3() {
+        // sRGB red lies within the Display P3 gamut
+        let [red, green, blue] = RgbSpace::DisplayP3.components(Color::rgb(255, 0, 0));
+        assert!((red - 0.9175).abs() < 1e-3);
+        assert!((green - 0.2003).abs() < 1e-3);
+        assert!((blue - 0.1386).abs() < 1e-3);
+    }
+
+    #[test]
+    fn keeps_out_of_gamut_colors() {
+        let color = RgbSpace::DisplayP3.color([1.0, 0.0, 0.0], 1.0);
+        assert!(!color.is_in_srgb_gamut());
+
+        let [red, green, blue] = RgbSpace::DisplayP3.components(color);
+        assert!((red - 1.0).abs() < 1e-4);
+        assert!(green.abs() < 1e-4);
+        assert!(blue.abs() < 1e-4);
+    }
+}
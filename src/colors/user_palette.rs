@@ -0,0 +1,160 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs, io,
+    path::PathBuf,
+};
+
+use once_cell::sync::Lazy;
+
+use super::{
+    color::Color,
+    color_names::{build_tree, NamedColor},
+};
+
+/// The palettes found in the user data directory, sorted by their identifier.
+///
+/// They are only loaded once, so changes require a restart.
+static PALETTES: Lazy<Vec<UserPalette>> = Lazy::new(load);
+
+/// A palette of color names loaded from a file in the user data directory.
+///
+/// The files use the same format as the bundled palettes, with one `name, #hex` pair per line.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug)]
+pub struct UserPalette {
+    /// The file name without its extension, which identifies the palette in the settings.
+    pub id: String,
+    /// The colors by their lowercase name.
+    colors: HashMap<String, Color>,
+    /// The names by the lowercase hex code of their color, including the alpha value.
+    names: HashMap<String, Vec<String>>,
+    tree: Vec<NamedColor<String>>,
+}
+
+impl UserPalette {
+    /// Parses a palette, skipping the lines without a valid six digit hex code.
+    pub fn parse(id: &str, content: &str) -> Self {
+        let mut colors = HashMap::new();
+        let mut names: HashMap<String, Vec<String>> = HashMap::new();
+        let mut tree = Vec::new();
+
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once(','))
+            .map(|(name, hex)| (name.trim(), hex.trim()))
+            .for_each(|(name, hex)| {
+                let Some(color) = parse_hex(hex) else {
+                    log::warn!("Invalid hex code {} for {} in palette {}", hex, name, id);
+                    return;
+                };
+                colors.insert(name.to_lowercase(), color);
+                match names.entry(color.hex()) {
+                    Entry::Occupied(mut entry) => entry.get_mut().push(name.to_owned()),
+                    // the tree only needs each color once, like the ones of the bundled palettes
+                    Entry::Vacant(entry) => {
+                        entry.insert(vec![name.to_owned()]);
+                        tree.push(NamedColor::new(name.to_owned(), color));
+                    }
+                }
+            });
+
+        build_tree(&mut tree, 0);
+        Self {
+            id: id.to_owned(),
+            colors,
+            names,
+            tree,
+        }
+    }
+
+    /// Returns the names of the color with the given lowercase hex code, such as `#2e3440ff`,
+    /// in the order of the file.
+    pub fn aliases(&self, hex: &str) -> &[String] {
+        self.names.get(hex).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the color with the given name, ignoring its case.
+    pub fn color(&self, name: &str) -> Option<Color> {
+        self.colors.get(&name.to_lowercase()).copied()
+    }
+
+    pub(super) fn tree(&'static self) -> &'static [NamedColor<String>] {
+        &self.tree
+    }
+}
+
+/// Parses a hex code with exactly six digits, such as `#2E3440`.
+fn parse_hex(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 {
+        return None;
+    }
+    let [_, red, green, blue] = u32::from_str_radix(hex, 16).ok()?.to_be_bytes();
+    Some(Color::rgb(red, green, blue))
+}
+
+/// The directory from which the palettes are loaded.
+pub fn directory() -> PathBuf {
+    glib::user_data_dir().join("eyedropper").join("palettes")
+}
+
+/// Returns all palettes found in the user data directory.
+pub fn all() -> &'static [UserPalette] {
+    &PALETTES
+}
+
+/// Returns the palette with the given identifier, if it exists.
+pub fn get(id: &str) -> Option<&'static UserPalette> {
+    PALETTES.iter().find(|palette| palette.id == id)
+}
+
+/// Loads all `.txt` files from the palette directory.
+fn load() -> Vec<UserPalette> {
+    let entries = match fs::read_dir(directory()) {
+        Ok(entries) => entries,
+        // most users will never create the directory
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(err) => {
+            log::warn!("Failed to read user palettes: {}", err);
+            return Vec::new();
+        }
+    };
+
+    let mut palettes = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_string_lossy().into_owned();
+            match fs::read_to_string(&path) {
+                Ok(content) => Some(UserPalette::parse(&id, &content)),
+                Err(err) => {
+                    log::warn!("Failed to read user palette {}: {}", path.display(), err);
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    palettes.sort_by(|a, b| a.id.cmp(&b.id));
+    palettes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_palettes() {
+        let palette = UserPalette::parse(
+            "brand",
+            "# Brand colors\nPolar Night, #2E3440\n\ninvalid, #2e344\nnight, #2e3440\n",
+        );
+
+        assert_eq!(Some(Color::rgb(46, 52, 64)), palette.color("polar night"));
+        assert_eq!(Some(Color::rgb(46, 52, 64)), palette.color("NIGHT"));
+        assert_eq!(None, palette.color("invalid"));
+        assert_eq!(["Polar Night", "night"], palette.aliases("#2e3440ff"));
+        assert_eq!(1, palette.tree.len());
+    }
+}
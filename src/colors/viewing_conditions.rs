@@ -0,0 +1,68 @@
+use palette::{
+    cam16::{BakedParameters, Parameters, StaticWp},
+    white_point::D65,
+};
+
+/// The luminance of the area surrounding the viewed colors.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Surround {
+    /// A surface color, such as a print viewed in a lit room.
+    #[default]
+    Average,
+    /// A dimly lit room with a bright display.
+    Dim,
+    /// A dark room, such as a movie theater.
+    Dark,
+}
+
+//Convert from U32. Needed for converting from the settings AdwComboRow, which use indexes for values.
+impl From<u32> for Surround {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Average,
+            1 => Self::Dim,
+            2 => Self::Dark,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// The viewing conditions used by the CAM16 color appearance model.
+///
+/// The appearance of a color depends on its environment, so the same color
+/// results in different CAM16 values under different viewing conditions.
+///
+/// Defaults to a display with the 80 cd/m² white of sRGB, in an environment that is
+/// 20% as bright as the white, with a medium gray background.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ViewingConditions {
+    /// The average luminance of the environment in cd/m².
+    pub adapting_luminance: f32,
+    /// The luminance of the background relative to the white, from 0.0 to 1.0.
+    pub background_luminance: f32,
+    pub surround: Surround,
+}
+
+impl ViewingConditions {
+    /// Returns the CAM16 parameters for these viewing conditions, adapted to the D65 white of the display.
+    pub fn parameters(&self) -> BakedParameters<StaticWp<D65>, f64> {
+        let mut parameters = Parameters::default_static_wp(self.adapting_luminance as f64);
+        parameters.background_luminance = self.background_luminance as f64;
+        parameters.surround = match self.surround {
+            Surround::Average => palette::cam16::Surround::Average,
+            Surround::Dim => palette::cam16::Surround::Dim,
+            Surround::Dark => palette::cam16::Surround::Dark,
+        };
+        parameters.bake()
+    }
+}
+
+impl Default for ViewingConditions {
+    fn default() -> Self {
+        Self {
+            adapting_luminance: 16.0,
+            background_luminance: 0.2,
+            surround: Surround::Average,
+        }
+    }
+}
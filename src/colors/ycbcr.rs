@@ -0,0 +1,197 @@
+use super::{
+    color::{round_half_up, Color},
+    rgb_space::RgbSpace,
+};
+
+/// The matrix used to derive the luma and chroma values from the gamma encoded RGB values.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum YcbcrMatrix {
+    /// ITU-R BT.601, used for SD video.
+    Bt601,
+    /// ITU-R BT.709, used for HD video.
+    #[default]
+    Bt709,
+    /// ITU-R BT.2020, used for UHD video. The RGB values use the BT.2020 primaries.
+    Bt2020,
+}
+
+//Convert from U32. Needed for converting from the settings AdwComboRow, which use indexes for values.
+impl From<u32> for YcbcrMatrix {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Bt601,
+            1 => Self::Bt709,
+            2 => Self::Bt2020,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// The range of codes used for the values.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum YcbcrRange {
+    /// The studio range, which leaves room below black and above white, e.g. 16 to 235 for 8-bit luma.
+    #[default]
+    Limited,
+    /// The full range of codes, e.g. 0 to 255 for 8-bit values.
+    Full,
+}
+
+//Convert from U32. Needed for converting from the settings AdwComboRow, which use indexes for values.
+impl From<u32> for YcbcrRange {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Limited,
+            1 => Self::Full,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// The digital encoding of YCbCr values, as used in video files.
+///
+/// Defaults to 8-bit BT.709 in limited range, the most common encoding for HD video.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct YcbcrEncoding {
+    pub matrix: YcbcrMatrix,
+    pub range: YcbcrRange,
+    /// The number of bits per value, usually 8 or 10.
+    pub bit_depth: u32,
+}
+
+impl Default for YcbcrEncoding {
+    fn default() -> Self {
+        Self {
+            matrix: YcbcrMatrix::default(),
+            range: YcbcrRange::default(),
+            bit_depth: 8,
+        }
+    }
+}
+
+impl YcbcrEncoding {
+    /// The weights of the red and blue components in the luma value.
+    fn luma_weights(&self) -> (f32, f32) {
+        match self.matrix {
+            YcbcrMatrix::Bt601 => (0.299, 0.114),
+            YcbcrMatrix::Bt709 => (0.2126, 0.0722),
+            YcbcrMatrix::Bt2020 => (0.2627, 0.0593),
+        }
+    }
+
+    /// Returns the scale and offset used to convert luma and chroma values into codes.
+    fn quantization(&self) -> [(f32, f32); 2] {
+        let max = (2u32.pow(self.bit_depth) - 1) as f32;
+        let half = 2u32.pow(self.bit_depth - 1) as f32;
+        match self.range {
+            YcbcrRange::Full => [(max, 0.0), (max, half)],
+            YcbcrRange::Limited => {
+                let scale = 2u32.pow(self.bit_depth - 8) as f32;
+                [(219.0 * scale, 16.0 * scale), (224.0 * scale, half)]
+            }
+        }
+    }
+
+    /// Returns the red, green and blue values the matrix is applied to.
+    fn rgb(&self, color: Color) -> [f32; 3] {
+        match self.matrix {
+            YcbcrMatrix::Bt2020 => RgbSpace::Rec2020.components(color),
+            // BT.601 and BT.709 video is commonly displayed without converting its primaries
+            _ => [color.red, color.green, color.blue],
+        }
+    }
+
+    /// Returns the Y, Cb and Cr codes of the color.
+    ///
+    /// The codes are limited to the values that can be stored with the bit depth.
+    pub fn components(&self, color: Color) -> [u32; 3] {
+        let (kr, kb) = self.luma_weights();
+        let [r, g, b] = self.rgb(color);
+
+        let y = kr * r + (1.0 - kr - kb) * g + kb * b;
+        let cb = (b - y) / (2.0 * (1.0 - kb));
+        let cr = (r - y) / (2.0 * (1.0 - kr));
+
+        let [(luma_scale, luma_offset), (chroma_scale, chroma_offset)] = self.quantization();
+        let max = (2u32.pow(self.bit_depth) - 1) as f32;
+        [
+            y * luma_scale + luma_offset,
+            cb * chroma_scale + chroma_offset,
+            cr * chroma_scale + chroma_offset,
+        ]
+        .map(|code| round_half_up(code).clamp(0.0, max) as u32)
+    }
+
+    /// Creates a color from the Y, Cb and Cr codes.
+    pub fn color(&self, codes: [f32; 3], alpha: f32) -> Color {
+        let [(luma_scale, luma_offset), (chroma_scale, chroma_offset)] = self.quantization();
+        let y = (codes[0] - luma_offset) / luma_scale;
+        let cb = (codes[1] - chroma_offset) / chroma_scale;
+        let cr = (codes[2] - chroma_offset) / chroma_scale;
+
+        let (kr, kb) = self.luma_weights();
+        let r = y + 2.0 * (1.0 - kr) * cr;
+        let b = y + 2.0 * (1.0 - kb) * cb;
+        let g = (y - kr * r - kb * b) / (1.0 - kr - kb);
+
+        match self.matrix {
+            YcbcrMatrix::Bt2020 => RgbSpace::Rec2020.color([r, g, b], alpha),
+            _ => Color::new(r, g, b, alpha),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_black_and_white() {
+        let encoding = YcbcrEncoding::default();
+        assert_eq!([16, 128, 128], encoding.components(Color::rgb(0, 0, 0)));
+        assert_eq!(
+            [235, 128, 128],
+            encoding.components(Color::rgb(255, 255, 255))
+        );
+
+        let encoding = YcbcrEncoding {
+            range: YcbcrRange::Full,
+            bit_depth: 10,
+            ..Default::default()
+        };
+        assert_eq!([0, 512, 512], encoding.components(Color::rgb(0, 0, 0)));
+        assert_eq!(
+            [1023, 512, 512],
+            encoding.components(Color::rgb(255, 255, 255))
+        );
+    }
+
+    #[test]
+    fn encodes_with_each_matrix() {
+        let red = Color::rgb(255, 0, 0);
+        let encode = |matrix| {
+            YcbcrEncoding {
+                matrix,
+                ..Default::default()
+            }
+            .components(red)
+        };
+
+        assert_eq!([81, 90, 240], encode(YcbcrMatrix::Bt601));
+        assert_eq!([63, 102, 240], encode(YcbcrMatrix::Bt709));
+    }
+
+    #[test]
+    fn round_trips() {
+        let color = Color::rgb(46, 52, 64);
+        for matrix in [YcbcrMatrix::Bt601, YcbcrMatrix::Bt709, YcbcrMatrix::Bt2020] {
+            let encoding = YcbcrEncoding {
+                matrix,
+                range: YcbcrRange::Full,
+                bit_depth: 10,
+            };
+            let codes = encoding.components(color).map(|code| code as f32);
+            assert_eq!(color.rgba8(), encoding.color(codes, 1.0).rgba8());
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use glib::Object;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+use gtk::{cairo, gio, glib, graphene};
+
+use crate::colors::{color::Color, illuminant::ReferenceWhite, rgb_space::RgbSpace};
+use crate::model::history::HistoryObject;
+
+/// The xy chromaticity coordinates of the sRGB primaries.
+const SRGB_PRIMARIES: [(f32, f32); 3] = [(0.640, 0.330), (0.300, 0.600), (0.150, 0.060)];
+
+/// The chromaticity of the monochromatic colors between 380nm and 700nm in 5nm steps,
+/// for the CIE 1931 2° standard observer.
+const SPECTRAL_LOCUS: [(f32, f32); 65] = [
+    (0.1741, 0.0050),
+    (0.1740, 0.0050),
+    (0.1738, 0.0049),
+    (0.1736, 0.0049),
+    (0.1733, 0.0048),
+    (0.1730, 0.0048),
+    (0.1726, 0.0048),
+    (0.1721, 0.0048),
+    (0.1714, 0.0051),
+    (0.1703, 0.0058),
+    (0.1689, 0.0069),
+    (0.1669, 0.0086),
+    (0.1644, 0.0109),
+    (0.1611, 0.0138),
+    (0.1566, 0.0177),
+    (0.1510, 0.0227),
+    (0.1440, 0.0297),
+    (0.1355, 0.0399),
+    (0.1241, 0.0578),
+    (0.1096, 0.0868),
+    (0.0913, 0.1327),
+    (0.0687, 0.2007),
+    (0.0454, 0.2950),
+    (0.0235, 0.4127),
+    (0.0082, 0.5384),
+    (0.0039, 0.6548),
+    (0.0139, 0.7502),
+    (0.0389, 0.8120),
+    (0.0743, 0.8338),
+    (0.1142, 0.8262),
+    (0.1547, 0.8059),
+    (0.1929, 0.7816),
+    (0.2296, 0.7543),
+    (0.2658, 0.7243),
+    (0.3016, 0.6923),
+    (0.3373, 0.6589),
+    (0.3731, 0.6245),
+    (0.4087, 0.5896),
+    (0.4441, 0.5547),
+    (0.4788, 0.5202),
+    (0.5125, 0.4866),
+    (0.5448, 0.4544),
+    (0.5752, 0.4242),
+    (0.6029, 0.3965),
+    (0.6270, 0.3725),
+    (0.6482, 0.3514),
+    (0.6658, 0.3340),
+    (0.6801, 0.3197),
+    (0.6915, 0.3083),
+    (0.7006, 0.2993),
+    (0.7079, 0.2920),
+    (0.7140, 0.2859),
+    (0.7190, 0.2809),
+    (0.7230, 0.2770),
+    (0.7260, 0.2740),
+    (0.7283, 0.2717),
+    (0.7300, 0.2700),
+    (0.7311, 0.2689),
+    (0.7320, 0.2680),
+    (0.7327, 0.2673),
+    (0.7334, 0.2666),
+    (0.7340, 0.2660),
+    (0.7344, 0.2656),
+    (0.7346, 0.2654),
+    (0.7347, 0.2653),
+];
+
+/// The largest x and y coordinates shown in the diagram.
+const MAX_X: f64 = 0.8;
+const MAX_Y: f64 = 0.9;
+/// The space around the diagram in pixels, so the points at the edges are not cut off.
+const PADDING: f64 = 8.0;
+
+mod imp {
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct ChromaticityDiagram {
+        pub color: Cell<Option<Color>>,
+        pub history: RefCell<Option<gio::ListStore>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ChromaticityDiagram {
+        const NAME: &'static str = "ChromaticityDiagram";
+        type Type = super::ChromaticityDiagram;
+        type ParentType = gtk::Widget;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.set_css_name("chromaticity-diagram");
+            klass.set_accessible_role(gtk::AccessibleRole::Img);
+        }
+    }
+
+    impl ObjectImpl for ChromaticityDiagram {}
+
+    impl WidgetImpl for ChromaticityDiagram {
+        fn snapshot(&self, snapshot: &gtk::Snapshot) {
+            let widget = self.obj();
+            let (width, height) = (widget.width() as f32, widget.height() as f32);
+            let context = snapshot.append_cairo(&graphene::Rect::new(0.0, 0.0, width, height));
+
+            if let Err(err) = self.draw(&context, width as f64, height as f64) {
+                log::error!("Failed to draw chromaticity diagram: {}", err);
+            }
+        }
+    }
+
+    impl ChromaticityDiagram {
+        /// Draws the spectral locus, the gamut triangles and the colors.
+        fn draw(
+            &self,
+            context: &cairo::Context,
+            width: f64,
+            height: f64,
+        ) -> Result<(), cairo::Error> {
+            let foreground = self.obj().color();
+            let set_foreground = |alpha: f32| {
+                context.set_source_rgba(
+                    foreground.red() as f64,
+                    foreground.green() as f64,
+                    foreground.blue() as f64,
+                    (foreground.alpha() * alpha) as f64,
+                )
+            };
+
+            // keep the aspect ratio and center the diagram
+            let scale = ((width - 2.0 * PADDING) / MAX_X).min((height - 2.0 * PADDING) / MAX_Y);
+            let (offset_x, offset_y) = (
+                (width - scale * MAX_X) / 2.0,
+                (height + scale * MAX_Y) / 2.0,
+            );
+            let point =
+                |(x, y): (f32, f32)| (offset_x + x as f64 * scale, offset_y - y as f64 * scale);
+            let polygon = |points: &[(f32, f32)]| {
+                context.new_path();
+                for &xy in points {
+                    let (x, y) = point(xy);
+                    context.line_to(x, y);
+                }
+                context.close_path();
+            };
+
+            context.set_line_width(1.0);
+            polygon(&SPECTRAL_LOCUS);
+            set_foreground(0.08);
+            context.fill_preserve()?;
+            set_foreground(0.5);
+            context.stroke()?;
+
+            polygon(&SRGB_PRIMARIES);
+            set_foreground(0.8);
+            context.stroke()?;
+
+            polygon(&RgbSpace::DisplayP3.primaries());
+            context.set_dash(&[4.0, 3.0], 0.0);
+            set_foreground(0.6);
+            context.stroke()?;
+            context.set_dash(&[], 0.0);
+
+            let current = self.color.get();
+            let history = self.history.borrow();
+            let history_colors = history
+                .iter()
+                .flat_map(|history| history.iter::<HistoryObject>())
+                .filter_map(|item| item.ok().map(|item| Color::from(item.color())))
+                .filter(|color| Some(*color) != current);
+
+            // draw the current color last, so it is on top of the history
+            for (color, radius) in history_colors
+                .map(|color| (color, 3.0))
+                .chain(current.map(|color| (color, 5.0)))
+            {
+                let xyy = color.to_xyy(ReferenceWhite::srgb());
+                let (x, y) = point((xyy.x, xyy.y));
+                let [red, green, blue, _] = color.rgba8().map(|value| value as f64 / 255.0);
+
+                context.new_path();
+                context.arc(x, y, radius, 0.0, std::f64::consts::TAU);
+                context.set_source_rgb(red, green, blue);
+                context.fill_preserve()?;
+                set_foreground(if Some(color) == current { 1.0 } else { 0.5 });
+                context.stroke()?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+glib::wrapper! {
+    /// Plots the current color and the history on the CIE 1931 xy chromaticity diagram,
+    /// together with the gamuts of sRGB and Display P3.
+    pub struct ChromaticityDiagram(ObjectSubclass<imp::ChromaticityDiagram>)
+    @extends gtk::Widget,
+    @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl ChromaticityDiagram {
+    pub fn new() -> Self {
+        Object::new()
+    }
+
+    /// Highlights the color as the current color.
+    pub fn set_color(&self, color: Color) {
+        self.imp().color.set(Some(color));
+        self.queue_draw();
+    }
+
+    /// Plots the colors of the history, redrawing the diagram whenever it changes.
+    pub fn set_history(&self, history: &gio::ListStore) {
+        history.connect_items_changed(glib::clone!(
+            #[weak(rename_to = diagram)]
+            self,
+            move |_, _, _, _| {
+                diagram.queue_draw();
+            }
+        ));
+        self.imp().history.replace(Some(history.clone()));
+        self.queue_draw();
+    }
+}
+
+impl Default for ChromaticityDiagram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
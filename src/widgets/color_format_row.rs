@@ -4,20 +4,17 @@ use gettextrs::gettext;
 use glib::translate::IntoGlib;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
-use gtk::{glib, prelude::ObjectExt};
+use gtk::{gio, glib, prelude::ObjectExt};
 
 use crate::colors::color::Color;
-use crate::colors::color_names::ColorNameSources;
-use crate::colors::position::AlphaPosition;
-use crate::colors::Notation;
+use crate::colors::color_names::{self, ColorNameSources, NameMatch};
+use crate::colors::diagnostic::Diagnostic;
+use crate::colors::{Notation, NotationOptions};
 
 mod imp {
     use std::cell::{Cell, RefCell};
 
-    use crate::{
-        colors::{self, color_names::ColorNameSources},
-        config,
-    };
+    use crate::{colors, config};
 
     use super::*;
 
@@ -33,6 +30,10 @@ mod imp {
         #[template_child]
         pub entry: TemplateChild<gtk::Entry>,
         #[template_child]
+        pub approximation_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub alias_button: TemplateChild<gtk::MenuButton>,
+        #[template_child]
         pub format_button: TemplateChild<gtk::Button>,
         #[property(set, get)]
         pub tooltip: RefCell<String>,
@@ -40,6 +41,15 @@ mod imp {
         pub color: RefCell<String>,
         #[property(construct_only, get, builder(colors::Notation::default()))]
         pub color_format: Cell<colors::Notation>,
+        /// The diagnostics of the last entered value, shown until the entry is edited.
+        pub diagnostics: RefCell<Vec<Diagnostic>>,
+        pub out_of_gamut: Cell<bool>,
+        /// All names of the closest named color, if the row shows names.
+        pub aliases: RefCell<Vec<color_names::Alias>>,
+        /// The named color and palettes the aliases have been listed for.
+        pub aliases_of: RefCell<Option<(Color, ColorNameSources, Vec<String>)>>,
+        /// The alias chosen by the user, which is shown whenever a color has it.
+        pub chosen_alias: RefCell<Option<String>>,
     }
 
     impl Default for ColorFormatRow {
@@ -47,10 +57,17 @@ mod imp {
             Self {
                 settings: gtk::gio::Settings::new(config::APP_ID),
                 entry: TemplateChild::default(),
+                approximation_label: TemplateChild::default(),
+                alias_button: TemplateChild::default(),
                 format_button: TemplateChild::default(),
                 tooltip: RefCell::default(),
                 color: RefCell::default(),
                 color_format: Cell::default(),
+                diagnostics: RefCell::default(),
+                out_of_gamut: Cell::default(),
+                aliases: RefCell::default(),
+                aliases_of: RefCell::default(),
+                chosen_alias: RefCell::default(),
             }
         }
     }
@@ -64,6 +81,19 @@ mod imp {
         fn class_init(klass: &mut Self::Class) {
             klass.bind_template();
             klass.bind_template_instance_callbacks();
+
+            klass.install_action(
+                "format-row.choose-alias",
+                Some(glib::VariantTy::STRING),
+                |row, _action_name, parameter| {
+                    let Some(name) = parameter.and_then(|parameter| parameter.get::<String>())
+                    else {
+                        return;
+                    };
+                    row.show_alias(&name);
+                    row.imp().chosen_alias.replace(Some(name));
+                },
+            );
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -92,18 +122,22 @@ mod imp {
                 move |entry| {
                     let obj = widget.obj();
                     let text = entry.buffer().text();
-                    let name_flags =
-                        ColorNameSources::from_bits(widget.settings.uint("name-sources-flag"))
-                            .unwrap_or(ColorNameSources::empty());
-                    let Ok(color) = obj.color_format().parse(text.as_str(), name_flags) else {
-                        log::debug!("Failed to parse color: {}", text);
-                        obj.show_error();
-                        return;
-                    };
+                    let (color, diagnostics) =
+                        match obj.color_format().parse_diagnosed(text.as_str()) {
+                            Ok(result) => result,
+                            Err(diagnostic) => {
+                                log::debug!("Failed to parse color: {}: {}", text, diagnostic);
+                                obj.show_diagnostics(vec![diagnostic], true);
+                                obj.show_error();
+                                return;
+                            }
+                        };
                     obj.display_color(color);
+                    // the entered text has been replaced by the formatted color, so there is nothing to underline
+                    obj.show_diagnostics(diagnostics, false);
                     obj.show_success();
 
-                    obj.activate_action("win.set-color", Some(&color.hex().to_variant()))
+                    obj.activate_action("win.set-color", Some(&color.to_variant()))
                         .expect("Failed to set color");
                 }
             ));
@@ -113,6 +147,9 @@ mod imp {
                 obj,
                 move |_entry| {
                     obj.switch_button(obj.text_changed());
+                    if !obj.imp().diagnostics.borrow().is_empty() {
+                        obj.show_diagnostics(Vec::new(), false);
+                    }
                 }
             ));
         }
@@ -146,15 +183,173 @@ impl ColorFormatRow {
     /// The displayed color format is determined by the `color_format` of
     /// the widget.
     pub fn display_color(&self, color: Color) {
-        let alpha_position = AlphaPosition::from(self.imp().settings.int("alpha-position") as u32);
-        let precision = self.imp().settings.uint("precision-digits") as usize;
-        let name_sources =
-            ColorNameSources::from_bits(self.imp().settings.uint("name-sources-flag"))
-                .unwrap_or(ColorNameSources::empty());
-        let color = self
-            .color_format()
-            .as_str(color, alpha_position, precision, name_sources);
-        self.set_color(color);
+        let options = NotationOptions::from_settings(&self.imp().settings);
+        if self.color_format() == Notation::Name {
+            let (name, found) = Notation::format_name(color, &options);
+            self.set_color(name);
+            self.update_aliases(found, &options);
+        } else {
+            self.set_color(self.color_format().as_str(color, &options));
+        }
+        self.show_gamut_warning(
+            !color.is_in_srgb_gamut() && self.color_format().is_limited_to_srgb(),
+        );
+    }
+
+    /// Lists the names of the closest named color in the menu next to the entry,
+    /// which is only shown if there is more than one.
+    ///
+    /// The alias chosen before is kept, as long as the color has it as well.
+    /// The menu is only rebuilt if the named color or the enabled palettes have changed.
+    fn update_aliases(&self, found: Option<NameMatch>, options: &NotationOptions) {
+        let imp = self.imp();
+        let aliases_of = found.map(|found| {
+            (
+                found.color,
+                options.name_sources,
+                options.user_name_sources.clone(),
+            )
+        });
+        if *imp.aliases_of.borrow() != aliases_of {
+            let aliases = found
+                .map(|found| {
+                    color_names::aliases(
+                        found.color,
+                        options.name_sources,
+                        &options.user_name_sources,
+                    )
+                })
+                .unwrap_or_default();
+
+            let menu = gio::Menu::new();
+            for alias in &aliases {
+                let label = format!("{} ({})", alias.name, alias.source.label());
+                let item = gio::MenuItem::new(Some(&label), None);
+                item.set_action_and_target_value(
+                    Some("format-row.choose-alias"),
+                    Some(&alias.name.to_variant()),
+                );
+                menu.append_item(&item);
+            }
+            imp.alias_button.set_menu_model(Some(&menu));
+            imp.alias_button.set_visible(aliases.len() > 1);
+            imp.aliases.replace(aliases);
+            imp.aliases_of.replace(aliases_of);
+        }
+
+        self.show_approximation(found);
+        let chosen_alias = imp.chosen_alias.borrow().clone();
+        if let Some(name) = chosen_alias {
+            self.show_alias(&name);
+        }
+    }
+
+    /// Shows the alias with the given name instead of the first name of the color,
+    /// so that it is copied instead.
+    fn show_alias(&self, name: &str) {
+        let alias = self
+            .imp()
+            .aliases
+            .borrow()
+            .iter()
+            .find(|alias| alias.name == name)
+            .copied();
+        if let Some(alias) = alias {
+            self.set_color(alias.name.to_owned());
+        }
+    }
+
+    /// Marks an approximate name with its difference to the named color, such as `≈ ΔE 1.2`.
+    ///
+    /// The marker is shown next to the entry instead of in its text, so that only the name is copied.
+    fn show_approximation(&self, found: Option<NameMatch>) {
+        let label = &self.imp().approximation_label;
+        match found.filter(|found| !found.is_exact()) {
+            Some(found) => {
+                let distance = format!("{:.1}", found.distance);
+                label.set_label(&format!("≈ ΔE {}", distance));
+                label.set_tooltip_text(Some(
+                    &gettext("The closest named color differs by ΔE {}").replace("{}", &distance),
+                ));
+                label.set_visible(true);
+            }
+            None => label.set_visible(false),
+        }
+    }
+
+    /// Shows a warning icon inside the entry, indicating that the displayed value has been clamped,
+    /// as the color lies outside of the sRGB gamut.
+    fn show_gamut_warning(&self, show: bool) {
+        self.imp().out_of_gamut.set(show);
+        self.update_secondary_icon();
+    }
+
+    /// Explains what was wrong with the entered value, using an icon with a tooltip inside the entry.
+    ///
+    /// If `underline` is set to true, the parts of the entered text the diagnostics refer to
+    /// are underlined as well.
+    /// The diagnostics are shown until they are replaced, which happens when the entry is edited.
+    fn show_diagnostics(&self, diagnostics: Vec<Diagnostic>, underline: bool) {
+        let entry = &self.imp().entry;
+        let attributes = gtk::pango::AttrList::new();
+        if underline {
+            for diagnostic in &diagnostics {
+                let mut attribute =
+                    gtk::pango::AttrInt::new_underline(gtk::pango::Underline::Error);
+                attribute.set_start_index(diagnostic.span.start as u32);
+                // an empty span at the end of the input still needs some space for the underline
+                attribute.set_end_index(diagnostic.span.end.max(diagnostic.span.start + 1) as u32);
+                attributes.insert(attribute);
+            }
+        }
+        entry.set_attributes(&attributes);
+
+        self.imp().diagnostics.replace(diagnostics);
+        self.update_secondary_icon();
+    }
+
+    /// Updates the icon inside the entry, preferring the diagnostics of the entered value
+    /// over the gamut warning of the displayed color.
+    fn update_secondary_icon(&self) {
+        let imp = self.imp();
+        let entry = &imp.entry;
+        let diagnostics = imp.diagnostics.borrow();
+        let is_error = diagnostics.iter().any(Diagnostic::is_error);
+
+        let (icon, tooltip) = if !diagnostics.is_empty() {
+            let icon = if is_error {
+                "dialog-error-symbolic"
+            } else {
+                "dialog-warning-symbolic"
+            };
+            let messages = diagnostics
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            (Some(icon), Some(messages.join("\n")))
+        } else if imp.out_of_gamut.get() {
+            (
+                Some("dialog-warning-symbolic"),
+                Some(gettext(
+                    "The color is outside of the sRGB gamut and has been clamped",
+                )),
+            )
+        } else {
+            (None, None)
+        };
+
+        entry.set_secondary_icon_name(icon);
+        entry.set_secondary_icon_tooltip_text(tooltip.as_deref());
+        if is_error {
+            entry.add_css_class("error");
+        } else {
+            entry.remove_css_class("error");
+        }
+        if icon.is_some() && !is_error {
+            entry.add_css_class("warning");
+        } else {
+            entry.remove_css_class("warning");
+        }
     }
 
     /// Switches the button next to the entry.
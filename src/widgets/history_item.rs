@@ -7,9 +7,7 @@ mod imp {
 
     use std::cell::Cell;
 
-    use crate::colors::{
-        color::Color, color_names::ColorNameSources, position::AlphaPosition, Notation,
-    };
+    use crate::colors::{color::Color, Notation, NotationOptions};
 
     use super::*;
 
@@ -50,14 +48,14 @@ mod imp {
             klass.install_action("history.remove", None, |item, _, _value| {
                 item.activate_action(
                     "win.remove-item",
-                    Some(&Color::from(item.color()).hex().to_variant()),
+                    Some(&Color::from(item.color()).to_variant()),
                 )
                 .expect("Failed to call win.set-color action");
             });
             klass.install_action("history.clicked", None, |item, _, _value| {
                 item.activate_action(
                     "win.set-color",
-                    Some(&Color::from(item.color()).hex().to_variant()),
+                    Some(&Color::from(item.color()).to_variant()),
                 )
                 .expect("Failed to call win.set-color action");
             });
@@ -108,12 +106,7 @@ mod imp {
             if color.alpha() != 1.0 {
                 Color::from(color).hex()
             } else {
-                Notation::Hex.as_str(
-                    color.into(),
-                    AlphaPosition::None,
-                    2,
-                    ColorNameSources::empty(),
-                )
+                Notation::Hex.as_str(color.into(), &NotationOptions::default())
             }
         }
     }
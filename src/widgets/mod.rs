@@ -1,4 +1,5 @@
 pub mod about_window;
+pub mod chromaticity_diagram;
 pub mod color_format_row;
 pub mod history_item;
 pub mod preferences;
@@ -12,6 +12,7 @@ use gtk::subclass::prelude::*;
 use gtk::Switch;
 
 use crate::colors::color::Color;
+use crate::colors::user_palette;
 use crate::colors::Notation;
 
 use super::color_format::ColorFormatObject;
@@ -36,10 +37,44 @@ mod imp {
         #[template_child()]
         pub name_source_page: TemplateChild<adw::NavigationPage>,
         #[template_child()]
+        pub colorimetry_page: TemplateChild<adw::NavigationPage>,
+        #[template_child()]
+        pub illuminant_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
+        pub observer_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
+        pub sdr_white_row: TemplateChild<adw::SpinRow>,
+        #[template_child()]
+        pub adapting_luminance_row: TemplateChild<adw::SpinRow>,
+        #[template_child()]
+        pub background_luminance_row: TemplateChild<adw::SpinRow>,
+        #[template_child()]
+        pub surround_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
         pub alpha_pos_box: TemplateChild<adw::ComboRow>,
         #[template_child()]
         pub precision_row: TemplateChild<adw::SpinRow>,
         #[template_child()]
+        pub hex_short_form_row: TemplateChild<adw::SwitchRow>,
+        #[template_child()]
+        pub hex_uppercase_row: TemplateChild<adw::SwitchRow>,
+        #[template_child()]
+        pub hex_prefix_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
+        pub css_rgb_syntax_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
+        pub css_hsl_syntax_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
+        pub css_hwb_syntax_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
+        pub css_color_space_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
+        pub ycbcr_matrix_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
+        pub ycbcr_range_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
+        pub ycbcr_bit_depth_row: TemplateChild<adw::ComboRow>,
+        #[template_child()]
         pub order_list: TemplateChild<gtk::ListBox>,
         #[template_child]
         pub(super) name_source_basic: TemplateChild<adw::SwitchRow>,
@@ -49,6 +84,13 @@ mod imp {
         pub(super) name_source_gnome: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub(super) name_source_xkcd: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub(super) name_source_iscc_nbs: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub(super) user_palettes_group: TemplateChild<adw::PreferencesGroup>,
+        pub(super) user_palette_rows: RefCell<Vec<adw::SwitchRow>>,
+        #[template_child]
+        pub(super) name_tolerance_row: TemplateChild<adw::SpinRow>,
         pub format_order: RefCell<Option<gio::ListStore>>,
     }
 
@@ -62,13 +104,34 @@ mod imp {
             Self {
                 settings: gtk::gio::Settings::new(config::APP_ID),
                 name_source_page: TemplateChild::default(),
+                colorimetry_page: TemplateChild::default(),
+                illuminant_row: TemplateChild::default(),
+                observer_row: TemplateChild::default(),
+                sdr_white_row: TemplateChild::default(),
+                adapting_luminance_row: TemplateChild::default(),
+                background_luminance_row: TemplateChild::default(),
+                surround_row: TemplateChild::default(),
                 alpha_pos_box: TemplateChild::default(),
                 precision_row: TemplateChild::default(),
+                hex_short_form_row: TemplateChild::default(),
+                hex_uppercase_row: TemplateChild::default(),
+                hex_prefix_row: TemplateChild::default(),
+                css_rgb_syntax_row: TemplateChild::default(),
+                css_hsl_syntax_row: TemplateChild::default(),
+                css_hwb_syntax_row: TemplateChild::default(),
+                css_color_space_row: TemplateChild::default(),
+                ycbcr_matrix_row: TemplateChild::default(),
+                ycbcr_range_row: TemplateChild::default(),
+                ycbcr_bit_depth_row: TemplateChild::default(),
                 order_list: TemplateChild::default(),
                 name_source_basic: TemplateChild::default(),
                 name_source_extended: TemplateChild::default(),
                 name_source_gnome: TemplateChild::default(),
                 name_source_xkcd: TemplateChild::default(),
+                name_source_iscc_nbs: TemplateChild::default(),
+                user_palettes_group: TemplateChild::default(),
+                user_palette_rows: RefCell::default(),
+                name_tolerance_row: TemplateChild::default(),
                 format_order: Default::default(),
             }
         }
@@ -95,6 +158,8 @@ mod imp {
             self.bind_setting(&self.name_source_extended, ColorNameSources::Svg);
             self.bind_setting(&self.name_source_gnome, ColorNameSources::Gnome);
             self.bind_setting(&self.name_source_xkcd, ColorNameSources::Xkcd);
+            self.bind_setting(&self.name_source_iscc_nbs, ColorNameSources::IsccNbs);
+            obj.populate_user_palettes();
         }
 
         fn dispose(&self) {
@@ -158,6 +223,82 @@ impl PreferencesWindow {
         imp.settings
             .bind("precision-digits", &*imp.precision_row, "value")
             .build();
+
+        imp.settings
+            .bind("hex-short-form", &*imp.hex_short_form_row, "active")
+            .build();
+
+        imp.settings
+            .bind("hex-uppercase", &*imp.hex_uppercase_row, "active")
+            .build();
+
+        imp.settings
+            .bind("hex-prefix", &*imp.hex_prefix_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("css-rgb-syntax", &*imp.css_rgb_syntax_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("css-hsl-syntax", &*imp.css_hsl_syntax_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("css-hwb-syntax", &*imp.css_hwb_syntax_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("css-color-space", &*imp.css_color_space_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("ycbcr-matrix", &*imp.ycbcr_matrix_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("ycbcr-range", &*imp.ycbcr_range_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("ycbcr-bit-depth", &*imp.ycbcr_bit_depth_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("cie-illuminants", &*imp.illuminant_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("cie-standard-observer", &*imp.observer_row, "selected")
+            .build();
+
+        imp.settings
+            .bind("sdr-white-luminance", &*imp.sdr_white_row, "value")
+            .build();
+
+        imp.settings
+            .bind("name-tolerance", &*imp.name_tolerance_row, "value")
+            .build();
+
+        imp.settings
+            .bind(
+                "cam16-adapting-luminance",
+                &*imp.adapting_luminance_row,
+                "value",
+            )
+            .build();
+
+        imp.settings
+            .bind(
+                "cam16-background-luminance",
+                &*imp.background_luminance_row,
+                "value",
+            )
+            .build();
+
+        imp.settings
+            .bind("cam16-surround", &*imp.surround_row, "selected")
+            .build();
     }
 
     /// Resets the current order by resetting the setting and repopulating the list.
@@ -175,6 +316,105 @@ impl PreferencesWindow {
         self.push_subpage(&*self.imp().name_source_page);
     }
 
+    /// Adds a switch for each palette in the user data directory.
+    ///
+    /// The enabled palettes are listed first in the order of their priority, followed by
+    /// the disabled ones in alphabetical order.
+    fn populate_user_palettes(&self) {
+        let imp = self.imp();
+        for row in imp.user_palette_rows.take() {
+            imp.user_palettes_group.remove(&row);
+        }
+
+        imp.user_palettes_group.set_description(Some(
+            &gettext("Files with one “name, #hex” pair per line in {}")
+                .replace("{}", &user_palette::directory().display().to_string()),
+        ));
+
+        let enabled = imp.settings.get::<Vec<String>>("user-name-sources");
+        let priority = |id: &str| enabled.iter().position(|enabled| enabled == id);
+        let mut palettes = user_palette::all()
+            .iter()
+            .map(|palette| palette.id.clone())
+            .collect::<Vec<_>>();
+        palettes.sort_by_key(|id| priority(id).unwrap_or(usize::MAX));
+
+        let rows = palettes
+            .into_iter()
+            .map(|id| {
+                let row = adw::SwitchRow::builder()
+                    .title(id.as_str())
+                    .active(priority(&id).is_some())
+                    .build();
+
+                let button = gtk::Button::builder()
+                    .icon_name("go-up-symbolic")
+                    .tooltip_text(gettext("Increase Priority"))
+                    .valign(gtk::Align::Center)
+                    .sensitive(priority(&id).is_some_and(|priority| priority > 0))
+                    .build();
+                button.add_css_class("flat");
+                row.add_suffix(&button);
+
+                button.connect_clicked(glib::clone!(
+                    #[weak(rename_to = window)]
+                    self,
+                    #[strong]
+                    id,
+                    move |_button| {
+                        window.update_user_palettes(|enabled| {
+                            if let Some(index) = enabled.iter().position(|enabled| enabled == &id) {
+                                enabled.swap(index.saturating_sub(1), index);
+                            }
+                        });
+                    }
+                ));
+                row.connect_active_notify(glib::clone!(
+                    #[weak(rename_to = window)]
+                    self,
+                    move |row| {
+                        let active = row.is_active();
+                        window.update_user_palettes(|enabled| {
+                            enabled.retain(|enabled| enabled != &id);
+                            // newly enabled palettes have the lowest priority
+                            if active {
+                                enabled.push(id.clone());
+                            }
+                        });
+                    }
+                ));
+
+                imp.user_palettes_group.add(&row);
+                row
+            })
+            .collect();
+        imp.user_palette_rows.replace(rows);
+    }
+
+    /// Changes the enabled user palettes and reorders their switches afterwards.
+    fn update_user_palettes(&self, update: impl FnOnce(&mut Vec<String>)) {
+        let settings = &self.imp().settings;
+        let mut enabled = settings.get::<Vec<String>>("user-name-sources");
+        update(&mut enabled);
+        settings
+            .set("user-name-sources", enabled)
+            .expect("Failed to save user-name-sources");
+
+        // the rows are replaced once the signal handler of the changed row has returned
+        glib::idle_add_local_once(glib::clone!(
+            #[weak(rename_to = window)]
+            self,
+            move || window.populate_user_palettes()
+        ));
+    }
+
+    /// Shows a page letting the user choose the reference white used for CIE color spaces,
+    /// the luminance of the SDR white used for HDR color spaces and the CAM16 viewing conditions.
+    #[template_callback]
+    fn on_colorimetry_row_activated(&self, _row: &adw::ActionRow) {
+        self.push_subpage(&*self.imp().colorimetry_page);
+    }
+
     /// Returns the formats list store object.
     fn formats(&self) -> gio::ListStore {
         self.imp()
@@ -4,16 +4,26 @@ use gettextrs::gettext;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 use gtk::{gio, glib};
-use palette::{GetHue, IntoColor, SetHue};
+use palette::IntoColor;
 
 use crate::application::App;
 use crate::colors::color::Color;
 use crate::colors::Notation;
 use crate::config::{APP_ID, PROFILE};
 use crate::model::history::HistoryObject;
+use crate::widgets::chromaticity_diagram::ChromaticityDiagram;
 use crate::widgets::color_format_row::ColorFormatRow;
 use crate::widgets::history_item::HistoryItem;
 
+/// The color model used by the sliders of the edit sheet.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum SliderMode {
+    #[default]
+    Hsl,
+    Okhsl,
+    Okhsv,
+}
+
 mod imp {
     use std::cell::{Cell, OnceCell, RefCell};
 
@@ -32,6 +42,8 @@ mod imp {
         #[template_child]
         pub toast_overlay: TemplateChild<adw::ToastOverlay>,
         #[template_child]
+        pub color_input: TemplateChild<gtk::Entry>,
+        #[template_child]
         pub format_box: TemplateChild<gtk::Box>,
         #[template_child]
         pub color_button: TemplateChild<gtk::ColorDialogButton>,
@@ -42,6 +54,12 @@ mod imp {
         #[template_child]
         pub color_preview: TemplateChild<gtk::ColorDialogButton>,
         #[template_child]
+        pub hsl_toggle: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub okhsl_toggle: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub okhsv_toggle: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
         pub hue_scale: TemplateChild<gtk::Scale>,
         #[template_child]
         pub saturation_scale: TemplateChild<gtk::Scale>,
@@ -49,9 +67,12 @@ mod imp {
         pub lightness_scale: TemplateChild<gtk::Scale>,
         #[template_child]
         pub history_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub chromaticity_diagram: TemplateChild<ChromaticityDiagram>,
         pub history: OnceCell<gio::ListStore>,
         pub settings: gio::Settings,
         pub color: Cell<Option<Color>>,
+        pub slider_mode: Cell<SliderMode>,
         pub portal_error: RefCell<Option<ashpd::Error>>,
         pub css_provider: gtk::CssProvider,
     }
@@ -64,16 +85,22 @@ mod imp {
                 color_button: TemplateChild::default(),
                 color_picker_button: TemplateChild::default(),
                 toast_overlay: TemplateChild::default(),
+                color_input: TemplateChild::default(),
                 format_box: TemplateChild::default(),
                 edit_sheet: TemplateChild::default(),
+                hsl_toggle: TemplateChild::default(),
+                okhsl_toggle: TemplateChild::default(),
+                okhsv_toggle: TemplateChild::default(),
                 hue_scale: TemplateChild::default(),
                 saturation_scale: TemplateChild::default(),
                 lightness_scale: TemplateChild::default(),
                 color_preview: TemplateChild::default(),
                 history_list: TemplateChild::default(),
+                chromaticity_diagram: TemplateChild::default(),
                 history: Default::default(),
                 settings: gio::Settings::new(APP_ID),
                 color: Cell::new(None),
+                slider_mode: Cell::new(SliderMode::default()),
                 portal_error: RefCell::new(None),
                 css_provider: Default::default(),
             }
@@ -87,6 +114,7 @@ mod imp {
         type ParentType = adw::ApplicationWindow;
 
         fn class_init(klass: &mut Self::Class) {
+            ChromaticityDiagram::ensure_type();
             Self::bind_template(klass);
             Self::Type::bind_template_callbacks(klass);
 
@@ -102,12 +130,9 @@ mod imp {
 
             klass.install_action(
                 "win.set-color",
-                Some(glib::VariantTy::STRING),
+                Some(&Color::static_variant_type()),
                 move |win, _, var| {
-                    let Some(Ok(color)) = var
-                        .and_then(|v| v.get::<String>())
-                        .map(|v| Color::from_str(&v))
-                    else {
+                    let Some(color) = var.and_then(|v| v.get::<Color>()) else {
                         return;
                     };
                     win.set_color(color);
@@ -116,12 +141,9 @@ mod imp {
 
             klass.install_action(
                 "win.remove-item",
-                Some(glib::VariantTy::STRING),
+                Some(&Color::static_variant_type()),
                 |win, _, var| {
-                    let Some(Ok(color)) = var
-                        .and_then(|v| v.get::<String>())
-                        .map(|v| Color::from_str(&v))
-                    else {
+                    let Some(color) = var.and_then(|v| v.get::<Color>()) else {
                         return;
                     };
 
@@ -345,6 +367,9 @@ impl AppWindow {
                 history_item.upcast()
             });
 
+        // Plot the history on the chromaticity diagram
+        self.imp().chromaticity_diagram.set_history(self.history());
+
         // Assure that the history list is only visible when it is supposed to
         self.set_history_list_visible(self.history());
         self.history().connect_items_changed(glib::clone!(
@@ -512,6 +537,7 @@ impl AppWindow {
         self.update_stack();
 
         imp.color_button.set_rgba(&color.into());
+        imp.chromaticity_diagram.set_color(color);
 
         imp.format_box
             .observe_children()
@@ -521,39 +547,235 @@ impl AppWindow {
             .for_each(|row| row.display_color(color));
     }
 
-    /// Opens a bototm sheet with an HSL color picker.
+    /// Applies the color typed into the input bar, which can be in any notation.
+    #[template_callback]
+    fn on_color_input_activated(&self) {
+        let input = self.imp().color_input.text();
+        if self.apply_input(&input) {
+            self.imp().color_input.set_text("");
+        } else {
+            self.imp().color_input.add_css_class("error");
+        }
+    }
+
+    /// Removes the error style from the input bar once the user edits the input.
+    #[template_callback]
+    fn on_color_input_changed(&self) {
+        self.imp().color_input.remove_css_class("error");
+    }
+
+    /// Pastes a color in any notation from the clipboard.
+    ///
+    /// If a text field has the focus, the text is pasted into it instead.
+    pub fn paste_color(&self) {
+        if let Some(focus) = GtkWindowExt::focus(self).filter(|focus| focus.is::<gtk::Editable>()) {
+            if let Err(err) = focus.activate_action("clipboard.paste", None) {
+                log::error!("Failed to paste into text field: {}", err);
+            }
+            return;
+        }
+
+        self.paste_from_clipboard();
+    }
+
+    /// Pastes the clipboard using the paste button of the input bar.
+    #[template_callback]
+    fn on_color_input_icon_released(&self) {
+        self.paste_from_clipboard();
+    }
+
+    /// Reads the text of the clipboard and sets it as the current color, detecting its notation.
+    fn paste_from_clipboard(&self) {
+        let clipboard = self.clipboard();
+        let main_context = glib::MainContext::default();
+        main_context.spawn_local(glib::clone!(
+            #[weak(rename_to = window)]
+            self,
+            async move {
+                match clipboard.read_text_future().await {
+                    Ok(Some(text)) => {
+                        window.apply_input(&text);
+                    }
+                    Ok(None) => log::debug!("Clipboard does not contain text"),
+                    Err(err) => log::error!("Failed to read clipboard: {}", err),
+                }
+            }
+        ));
+    }
+
+    /// Sets the current color to the color in the input, detecting its notation.
+    ///
+    /// Shows a toast with the detected notation, and the other notations if the input is ambiguous.
+    /// Returns whether a color was found.
+    fn apply_input(&self, input: &str) -> bool {
+        let Some(detected) = Notation::detect(input) else {
+            log::debug!("Failed to detect color: {}", input);
+            // the input can be a whole clipboard, so only the start of its first line is shown
+            const MAX_CHARS: usize = 32;
+            let input = input.trim();
+            let first_line = input.lines().next().unwrap_or_default();
+            let shown = match first_line.char_indices().nth(MAX_CHARS) {
+                Some((end, _)) => format!("{}…", &first_line[..end]),
+                None if first_line.len() < input.len() => format!("{}…", first_line),
+                None => first_line.to_owned(),
+            };
+            self.show_toast(
+                gettext("No color found in “{}”").replace("{}", &shown),
+                adw::ToastPriority::Normal,
+            );
+            return false;
+        };
+
+        log::debug!("Detected {:?} in {}", detected.notation, input);
+        self.set_color(detected.color);
+
+        let text = if detected.is_ambiguous() {
+            let alternatives = detected
+                .alternatives
+                .iter()
+                .map(|(notation, _)| notation.label())
+                .collect::<Vec<_>>()
+                .join(", ");
+            gettext("Read as {notation}, could also be {alternatives}")
+                .replace("{notation}", &detected.notation.label())
+                .replace("{alternatives}", &alternatives)
+        } else {
+            gettext("Read as {notation}").replace("{notation}", &detected.notation.label())
+        };
+        self.show_toast(text, adw::ToastPriority::Normal);
+        true
+    }
+
+    /// Opens a bototm sheet with an HSL, OKHSL or OKHSV color picker.
     #[template_callback]
     fn open_sheet(&self) {
         let imp = self.imp();
-        imp.color_preview.set_rgba(&self.color().unwrap().into());
-        let hsl: palette::Hsl = self.color().unwrap().color.into_color();
-        imp.hue_scale
-            .set_value(hsl.get_hue().into_positive_degrees() as f64);
-        imp.saturation_scale
-            .set_value(hsl.saturation as f64 * 100.0);
-        imp.lightness_scale.set_value(hsl.lightness as f64 * 100.0);
+        let color = self.color().unwrap();
+        imp.color_preview.set_rgba(&color.into());
+        self.set_slider_values(color);
+        self.update_slider_gradients(color.into());
         imp.edit_sheet.set_open(true);
     }
 
+    /// Switches the sliders to the color model of the toggled button, keeping the preview color.
+    #[template_callback]
+    fn on_slider_mode_toggled(&self, button: gtk::ToggleButton) {
+        if !button.is_active() {
+            return;
+        }
+
+        let imp = self.imp();
+        let mode = if button == *imp.okhsl_toggle {
+            SliderMode::Okhsl
+        } else if button == *imp.okhsv_toggle {
+            SliderMode::Okhsv
+        } else {
+            SliderMode::Hsl
+        };
+        if mode == imp.slider_mode.get() {
+            return;
+        }
+
+        let color = Color::from(imp.color_preview.rgba());
+        imp.slider_mode.set(mode);
+        self.set_slider_values(color);
+        // setting the slider values may have moved the preview color slightly, so restore it
+        imp.color_preview.set_rgba(&color.into());
+        self.update_slider_gradients(color.into());
+    }
+
+    /// Sets the sliders to the hue, saturation and lightness or value of the color,
+    /// using the current slider mode.
+    fn set_slider_values(&self, color: Color) {
+        let imp = self.imp();
+        let (hue, saturation, lightness) = match imp.slider_mode.get() {
+            SliderMode::Hsl => {
+                let hsl: palette::Hsl = color.color.into_color();
+                (
+                    hsl.hue.into_positive_degrees(),
+                    hsl.saturation,
+                    hsl.lightness,
+                )
+            }
+            SliderMode::Okhsl => {
+                let okhsl: palette::Okhsl = color.color.into_color();
+                (
+                    okhsl.hue.into_positive_degrees(),
+                    okhsl.saturation,
+                    okhsl.lightness,
+                )
+            }
+            SliderMode::Okhsv => {
+                let okhsv: palette::Okhsv = color.color.into_color();
+                (
+                    okhsv.hue.into_positive_degrees(),
+                    okhsv.saturation,
+                    okhsv.value,
+                )
+            }
+        };
+        imp.hue_scale.set_value(hue as f64);
+        imp.saturation_scale.set_value(saturation as f64 * 100.0);
+        imp.lightness_scale.set_value(lightness as f64 * 100.0);
+    }
+
+    /// Creates a color from the hue, saturation and lightness or value, using the current slider mode.
+    ///
+    /// The saturation and lightness are expected between 0.0 and 1.0.
+    fn slider_color(&self, hue: f32, saturation: f32, lightness: f32) -> Color {
+        match self.imp().slider_mode.get() {
+            SliderMode::Hsl => Color::from_palette(palette::Hsl::new(hue, saturation, lightness)),
+            SliderMode::Okhsl => {
+                Color::from_palette(palette::Okhsl::new(hue, saturation, lightness))
+            }
+            SliderMode::Okhsv => {
+                Color::from_palette(palette::Okhsv::new(hue, saturation, lightness))
+            }
+        }
+    }
+
+    /// Updates the gradients of the hue and saturation scales.
+    ///
+    /// The hue gradient shows the fully saturated hues of the current slider mode,
+    /// the saturation gradient ends at the given color.
+    fn update_slider_gradients(&self, color: gtk::gdk::RGBA) {
+        // the lightness, at which the hues are the most vivid
+        let lightness = match self.imp().slider_mode.get() {
+            SliderMode::Hsl => 0.5,
+            SliderMode::Okhsl => 0.65,
+            SliderMode::Okhsv => 1.0,
+        };
+        let hue_stops = (0..=12)
+            .map(|step| {
+                let hue_color: gtk::gdk::RGBA =
+                    self.slider_color(step as f32 * 30.0, 1.0, lightness).into();
+                format!("{} {:.2}%", hue_color, step as f32 / 12.0 * 100.0)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        self.imp().css_provider.load_from_data(&format!(
+            ":root {{ --saturation-color: {}; --hue-gradient: linear-gradient(90deg, {}); }}",
+            color, hue_stops
+        ));
+    }
+
     /// Updates the preview color and color picker.
     #[template_callback]
     fn on_color_preview_updated(&self, scale: gtk::Scale) {
-        let mut hsl: palette::Hsl = Color::from(self.imp().color_preview.rgba())
-            .color
-            .into_color();
-
-        hsl.set_hue(self.imp().hue_scale.value() as f32);
-        hsl.lightness = self.imp().lightness_scale.value() as f32 / 100.0;
-        hsl.saturation = self.imp().saturation_scale.value() as f32 / 100.0;
+        let imp = self.imp();
+        let color = self.slider_color(
+            imp.hue_scale.value() as f32,
+            imp.saturation_scale.value() as f32 / 100.0,
+            imp.lightness_scale.value() as f32 / 100.0,
+        );
 
-        let gkd_color: gtk::gdk::RGBA = Color::from_palette(hsl).into();
-        self.imp().color_preview.set_rgba(&gkd_color);
+        let gkd_color: gtk::gdk::RGBA = color.into();
+        imp.color_preview.set_rgba(&gkd_color);
 
-        if scale != *self.imp().saturation_scale {
+        if scale != *imp.saturation_scale {
             // update gradient of the saturation_scale
-            self.imp()
-                .css_provider
-                .load_from_data(&format!(":root {{ --saturation-color: {}; }}", gkd_color));
+            self.update_slider_gradients(gkd_color);
         }
     }
 